@@ -0,0 +1,252 @@
+use std::str::FromStr;
+
+use pastelito_core::rule::Severity;
+
+pub(crate) const HELP: &str = r#"Lint Markdown and plaintext documents with pastelito
+
+Usage: pastelito [OPTIONS] [PATHS]...
+
+Arguments:
+  [PATHS]...  Files, directories or glob patterns to lint. Directories are
+              searched recursively for Markdown and text files. If no paths are
+              provided, read from stdin
+
+Options:
+      --format <FORMAT>  Force the input format, either `markdown` or
+                         `plaintext`. By default, the format is chosen from the
+                         file extension
+      --rules <FILE>     Load extra rules from a TOML or YAML rule file. Can
+                         be given more than once
+      --severity <LEVEL> Only report warnings at least as severe as `error`,
+                         `warning`, `info` or `hint`. Defaults to `hint`
+      --no-config        Do not read `pastelito.toml` config files
+      --json             Print the warnings as JSON
+  -q, --quiet            Do not print warnings, only set the exit code
+  -h, --help             Print help
+
+Configuration:
+  Rules and measures are configured by `pastelito.toml` files in the directory
+  of each input file and its parents. Settings in a directory override
+  settings from its parents
+
+Exit status:
+  0  No warnings were found
+  1  At least one warning was found
+  2  An error occurred"#;
+
+/// The format of an input document.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Format {
+    Markdown,
+    Plaintext,
+}
+
+impl Format {
+    /// Guess the format of a file from its extension.
+    ///
+    /// Anything that isn't obviously plaintext is treated as Markdown.
+    pub(crate) fn of_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("txt" | "text") => Format::Plaintext,
+            _ => Format::Markdown,
+        }
+    }
+}
+
+/// The style of the output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Output {
+    /// `file:line:col: message`
+    #[default]
+    Text,
+    /// A JSON array of warnings.
+    Json,
+    /// No output.
+    Quiet,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Args {
+    pub(crate) format: Option<Format>,
+    pub(crate) min_severity: Option<Severity>,
+    pub(crate) output: Output,
+    pub(crate) rule_files: Vec<String>,
+    pub(crate) use_config: bool,
+    pub(crate) paths: Vec<String>,
+}
+
+/// What the binary should do after parsing the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Command {
+    Help,
+    Lint(Args),
+}
+
+/// Parse the command line arguments, not including the program name.
+pub(crate) fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--format requires a value".to_string())?;
+                parsed.format = Some(parse_format(&value)?);
+            }
+            "--severity" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--severity requires a value".to_string())?;
+                parsed.min_severity = Some(parse_severity(&value)?);
+            }
+            "--rules" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--rules requires a value".to_string())?;
+                parsed.rule_files.push(value);
+            }
+            "--no-config" => parsed.use_config = false,
+            "--json" => parsed.output = Output::Json,
+            "-q" | "--quiet" => parsed.output = Output::Quiet,
+            "-h" | "--help" => return Ok(Command::Help),
+            "--" => {
+                parsed.paths.extend(args.by_ref());
+            }
+            _ => {
+                if let Some(value) = arg.strip_prefix("--format=") {
+                    parsed.format = Some(parse_format(value)?);
+                } else if let Some(value) = arg.strip_prefix("--severity=") {
+                    parsed.min_severity = Some(parse_severity(value)?);
+                } else if let Some(value) = arg.strip_prefix("--rules=") {
+                    parsed.rule_files.push(value.to_string());
+                } else if arg.starts_with('-') && arg != "-" {
+                    return Err(format!("Unknown option: {}", arg));
+                } else {
+                    parsed.paths.push(arg);
+                }
+            }
+        }
+    }
+
+    Ok(Command::Lint(parsed))
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "markdown" | "md" => Ok(Format::Markdown),
+        "plaintext" | "text" | "txt" => Ok(Format::Plaintext),
+        _ => Err(format!("Unknown format: {}", value)),
+    }
+}
+
+fn parse_severity(value: &str) -> Result<Severity, String> {
+    Severity::from_str(value).map_err(|_| format!("Unknown severity: {}", value))
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            format: None,
+            min_severity: None,
+            output: Output::default(),
+            rule_files: Vec::new(),
+            use_config: true,
+            paths: Vec::new(),
+        }
+    }
+}
+
+impl Args {
+    /// Does this invocation read from stdin?
+    pub(crate) fn is_stdin(&self) -> bool {
+        self.paths.is_empty() || self.paths == ["-"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn lint(args: &[&str]) -> Args {
+        match parse(args) {
+            Ok(Command::Lint(args)) => args,
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let args = lint(&[]);
+        assert_eq!(args, Args::default());
+        assert!(args.is_stdin());
+    }
+
+    #[test]
+    fn test_paths() {
+        let args = lint(&["a.md", "docs/", "**/*.md"]);
+        assert_eq!(args.paths, vec!["a.md", "docs/", "**/*.md"]);
+        assert!(!args.is_stdin());
+
+        let args = lint(&["--", "--json"]);
+        assert_eq!(args.paths, vec!["--json"]);
+
+        assert!(lint(&["-"]).is_stdin());
+    }
+
+    #[test]
+    fn test_options() {
+        assert_eq!(lint(&["--json"]).output, Output::Json);
+        assert!(lint(&[]).use_config);
+        assert!(!lint(&["--no-config"]).use_config);
+        assert_eq!(lint(&["-q"]).output, Output::Quiet);
+        assert_eq!(
+            lint(&["--format", "plaintext"]).format,
+            Some(Format::Plaintext)
+        );
+        assert_eq!(lint(&["--format=md"]).format, Some(Format::Markdown));
+        assert_eq!(
+            lint(&["--severity", "warning"]).min_severity,
+            Some(Severity::Warning)
+        );
+        assert_eq!(
+            lint(&["--severity=error"]).min_severity,
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            lint(&["--rules", "a.toml", "--rules=b.yaml"]).rule_files,
+            vec!["a.toml", "b.yaml"]
+        );
+        assert_eq!(parse(&["--help", "--json"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse(&["--format"]).is_err());
+        assert!(parse(&["--format", "html"]).is_err());
+        assert!(parse(&["--rules"]).is_err());
+        assert!(parse(&["--severity", "fatal"]).is_err());
+        assert!(parse(&["--unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_format_of_path() {
+        assert_eq!(
+            Format::of_path(std::path::Path::new("README.md")),
+            Format::Markdown
+        );
+        assert_eq!(
+            Format::of_path(std::path::Path::new("notes.txt")),
+            Format::Plaintext
+        );
+        assert_eq!(
+            Format::of_path(std::path::Path::new("LICENSE")),
+            Format::Markdown
+        );
+    }
+}
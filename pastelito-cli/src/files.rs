@@ -0,0 +1,179 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// File extensions which are linted when searching a directory.
+const EXTENSIONS: &[&str] = &["md", "markdown", "txt", "text"];
+
+/// Does `arg` look like a glob pattern rather than a plain path?
+fn is_glob(arg: &str) -> bool {
+    arg.contains(['*', '?', '['])
+}
+
+/// Does `path` have one of the extensions we lint by default?
+fn has_lintable_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXTENSIONS.contains(&ext))
+}
+
+/// Is this a hidden file or directory, such as `.git`?
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.') && name != "." && name != "..")
+}
+
+/// Recursively collect all lintable files in `dir`.
+fn walk_dir(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
+        if is_hidden(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            walk_dir(&path, files)?;
+        } else if has_lintable_extension(&path) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Add a single path to `files`. Directories are searched recursively.
+fn add_path(path: PathBuf, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if path.is_dir() {
+        walk_dir(&path, files)
+    } else if path.exists() {
+        files.push(path);
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{}: No such file or directory", path.display()),
+        ))
+    }
+}
+
+/// Expand the command line paths into a list of files to lint.
+///
+/// Each argument may be a file, a directory or a glob pattern. Files that are
+/// named explicitly are always linted, regardless of their extension. Files
+/// found by searching directories must have a Markdown or text extension.
+pub(crate) fn collect_files(args: &[String]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for arg in args {
+        if is_glob(arg) {
+            let paths =
+                glob::glob(arg).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            for path in paths {
+                let path = path.map_err(io::Error::from)?;
+                if path.is_dir() {
+                    walk_dir(&path, &mut files)?;
+                } else {
+                    files.push(path);
+                }
+            }
+        } else {
+            add_path(PathBuf::from(arg), &mut files)?;
+        }
+    }
+
+    // The same file may be named several times, for example via a directory
+    // and a glob.
+    let mut seen = std::collections::HashSet::new();
+    files.retain(|path| seen.insert(path.clone()));
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a temporary directory containing the given (empty) files.
+    fn with_tree(name: &str, files: &[&str], cb: impl FnOnce(&Path)) {
+        let root =
+            std::env::temp_dir().join(format!("pastelito-cli-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for file in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        cb(&root);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    fn relative(root: &Path, files: Vec<PathBuf>) -> Vec<String> {
+        files
+            .into_iter()
+            .map(|path| {
+                path.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_directory() {
+        with_tree(
+            "directory",
+            &["b.md", "a.txt", "sub/c.markdown", "image.png", ".git/d.md"],
+            |root| {
+                let files = collect_files(&[root.to_string_lossy().into_owned()]).unwrap();
+                assert_eq!(
+                    relative(root, files),
+                    vec!["a.txt", "b.md", "sub/c.markdown"]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_glob() {
+        with_tree(
+            "glob",
+            &["one.md", "two.md", "three.txt", "sub/four.md"],
+            |root| {
+                let pattern = root.join("*.md").to_string_lossy().into_owned();
+                let files = collect_files(&[pattern]).unwrap();
+                assert_eq!(relative(root, files), vec!["one.md", "two.md"]);
+
+                let pattern = root.join("**/*.md").to_string_lossy().into_owned();
+                let files = collect_files(&[pattern]).unwrap();
+                assert_eq!(
+                    relative(root, files),
+                    vec!["one.md", "sub/four.md", "two.md"]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_explicit_file() {
+        with_tree("explicit", &["LICENSE", "one.md"], |root| {
+            let license = root.join("LICENSE").to_string_lossy().into_owned();
+            let dir = root.to_string_lossy().into_owned();
+            let files = collect_files(&[license, dir]).unwrap();
+            assert_eq!(relative(root, files), vec!["LICENSE", "one.md"]);
+        });
+    }
+
+    #[test]
+    fn test_missing() {
+        assert!(collect_files(&["does/not/exist.md".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+mod args;
+mod files;
+mod output;
+
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, Read as _},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    rc::Rc,
+};
+
+use args::{Args, Command, Format, Output, HELP};
+use output::FileWarning;
+use pastelito_core::{
+    config::Config,
+    parsers::{MarkdownParser, PlaintextParser},
+    rule::{RuleSet, Severity},
+    rule_file, Document,
+};
+
+/// The parsers and rules shared by every input file.
+struct Linter {
+    markdown: MarkdownParser,
+    plaintext: PlaintextParser,
+    /// The rule set for each set of config files, so that files which share
+    /// the same config also share a rule set.
+    rulesets: HashMap<Vec<PathBuf>, Rc<RuleSet>>,
+    rule_files: Vec<String>,
+    use_config: bool,
+    min_severity: Severity,
+}
+
+fn invalid_data(err: impl fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+impl Linter {
+    fn new(args: &Args) -> Self {
+        Linter {
+            markdown: MarkdownParser::default(),
+            plaintext: PlaintextParser::default(),
+            rulesets: HashMap::new(),
+            rule_files: args.rule_files.clone(),
+            use_config: args.use_config,
+            min_severity: args.min_severity.unwrap_or(Severity::Hint),
+        }
+    }
+
+    /// Get the rule set for the document at `path`.
+    ///
+    /// This uses the `pastelito.toml` files which apply to `path`, plus the
+    /// rules from each of the `--rules` files.
+    fn ruleset(&mut self, path: &Path) -> io::Result<Rc<RuleSet>> {
+        let config = if self.use_config {
+            Config::discover(path).map_err(invalid_data)?
+        } else {
+            Config::default()
+        };
+
+        if let Some(ruleset) = self.rulesets.get(config.sources()) {
+            return Ok(ruleset.clone());
+        }
+
+        let mut ruleset = config.build_ruleset().map_err(invalid_data)?;
+        for rule_file in &self.rule_files {
+            let rules = rule_file::from_path(Path::new(rule_file))
+                .map_err(|err| invalid_data(format!("{}: {}", rule_file, err)))?;
+            ruleset.add_rules(rules);
+        }
+
+        let ruleset = Rc::new(ruleset);
+        self.rulesets
+            .insert(config.sources().to_vec(), ruleset.clone());
+        Ok(ruleset)
+    }
+
+    /// Lint a single document at `path`, appending any warnings to `warnings`.
+    ///
+    /// `name` is the name of the document in the output.
+    fn lint(
+        &mut self,
+        name: &str,
+        path: &Path,
+        input: &str,
+        format: Format,
+        warnings: &mut Vec<FileWarning>,
+    ) -> io::Result<()> {
+        let ruleset = self.ruleset(path)?;
+
+        let doc = match format {
+            Format::Markdown => Document::new(&self.markdown, input),
+            Format::Plaintext => Document::new(&self.plaintext, input),
+        };
+
+        let results = ruleset.apply(&doc);
+        warnings.extend(
+            results
+                .iter_warnings_with_ranges()
+                .filter(|(_, warning)| warning.severity <= self.min_severity)
+                .map(|(range, warning)| FileWarning::new(name, range, warning)),
+        );
+        Ok(())
+    }
+}
+
+fn run(args: Args) -> io::Result<Vec<FileWarning>> {
+    let mut linter = Linter::new(&args);
+    let mut warnings = Vec::new();
+
+    if args.is_stdin() {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        let format = args.format.unwrap_or(Format::Markdown);
+        linter.lint("<stdin>", Path::new("."), &input, format, &mut warnings)?;
+    } else {
+        for path in files::collect_files(&args.paths)? {
+            let input = std::fs::read_to_string(&path).map_err(|err| {
+                io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+            })?;
+            let format = args.format.unwrap_or_else(|| Format::of_path(&path));
+            linter.lint(&display_path(&path), &path, &input, format, &mut warnings)?;
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Format a path for output, without a redundant leading `./`.
+fn display_path(path: &Path) -> String {
+    path.strip_prefix(".")
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn main() -> ExitCode {
+    let args = match args::parse_args(std::env::args().skip(1)) {
+        Ok(Command::Lint(args)) => args,
+        Ok(Command::Help) => {
+            println!("{}", HELP);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            eprintln!("Try `pastelito --help` for more information.");
+            return ExitCode::from(2);
+        }
+    };
+
+    let output = args.output;
+
+    let warnings = match run(args) {
+        Ok(warnings) => warnings,
+        Err(err) => {
+            eprintln!("pastelito: {}", err);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut stdout = io::stdout().lock();
+    let written = match output {
+        Output::Text => output::write_text(&mut stdout, &warnings),
+        Output::Json => output::write_json(&mut stdout, &warnings),
+        Output::Quiet => Ok(()),
+    };
+
+    if let Err(err) = written {
+        eprintln!("pastelito: {}", err);
+        return ExitCode::from(2);
+    }
+
+    if warnings.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
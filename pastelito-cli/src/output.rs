@@ -0,0 +1,197 @@
+use std::io::{self, Write};
+
+use pastelito_core::{
+    readability::Grade,
+    rule::{Severity, Warning},
+    LineCharRange,
+};
+use serde_json::json;
+
+/// A warning from a single file, ready to be printed.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct FileWarning {
+    /// The name of the file, or `<stdin>`.
+    pub(crate) path: String,
+    /// The 1-based line of the start of the warning.
+    pub(crate) line: u32,
+    /// The 1-based column of the start of the warning, in UTF-16 code units.
+    pub(crate) column: u32,
+    /// The 1-based line of the end of the warning.
+    pub(crate) end_line: u32,
+    /// The 1-based column of the end of the warning, in UTF-16 code units.
+    pub(crate) end_column: u32,
+    /// The warning message.
+    pub(crate) message: String,
+    /// The id of the rule which produced the warning.
+    pub(crate) rule_id: String,
+    /// The severity of the warning.
+    pub(crate) severity: Severity,
+    /// The readability grade of the text, for readability rules.
+    pub(crate) grade: Option<Grade>,
+}
+
+impl FileWarning {
+    /// Create a new `FileWarning` from a warning and its range.
+    pub(crate) fn new(path: &str, range: LineCharRange, warning: &Warning) -> Self {
+        FileWarning {
+            path: path.to_string(),
+            line: range.start_line + 1,
+            column: range.start_char_utf16 + 1,
+            end_line: range.end_line + 1,
+            end_column: range.end_char_utf16 + 1,
+            message: warning.message.clone(),
+            rule_id: warning.rule_id.clone(),
+            severity: warning.severity,
+            grade: warning.grade,
+        }
+    }
+}
+
+/// Write the warnings in the `file:line:col: severity: message [rule-id]`
+/// format.
+pub(crate) fn write_text(out: &mut impl Write, warnings: &[FileWarning]) -> io::Result<()> {
+    for warning in warnings {
+        writeln!(
+            out,
+            "{}:{}:{}: {}: {} [{}]",
+            warning.path,
+            warning.line,
+            warning.column,
+            warning.severity.as_str(),
+            warning.message,
+            warning.rule_id
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write the warnings as a JSON array.
+pub(crate) fn write_json(out: &mut impl Write, warnings: &[FileWarning]) -> io::Result<()> {
+    let warnings = warnings
+        .iter()
+        .map(|warning| {
+            json!({
+                "path": warning.path,
+                "line": warning.line,
+                "column": warning.column,
+                "end_line": warning.end_line,
+                "end_column": warning.end_column,
+                "message": warning.message,
+                "rule_id": warning.rule_id,
+                "severity": warning.severity.as_str(),
+                "grade": warning.grade.map(|grade| grade.value()),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_writer_pretty(&mut *out, &warnings)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warnings() -> Vec<FileWarning> {
+        vec![
+            FileWarning {
+                path: "a.md".into(),
+                line: 1,
+                column: 5,
+                end_line: 1,
+                end_column: 12,
+                message: "Weasel words".into(),
+                rule_id: "weasel-words".into(),
+                severity: Severity::Warning,
+                grade: None,
+            },
+            FileWarning {
+                path: "b.md".into(),
+                line: 3,
+                column: 1,
+                end_line: 4,
+                end_column: 2,
+                message: "Passive construction".into(),
+                rule_id: "passive-construction".into(),
+                severity: Severity::Info,
+                grade: None,
+            },
+            FileWarning {
+                path: "b.md".into(),
+                line: 5,
+                column: 1,
+                end_line: 5,
+                end_column: 80,
+                message: "Hard to read (grade 11.0)".into(),
+                rule_id: "hard-sentences".into(),
+                severity: Severity::Info,
+                grade: Some(Grade::new(11.0)),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_text() {
+        let mut out = Vec::new();
+        write_text(&mut out, &warnings()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a.md:1:5: warning: Weasel words [weasel-words]\n\
+             b.md:3:1: info: Passive construction [passive-construction]\n\
+             b.md:5:1: info: Hard to read (grade 11.0) [hard-sentences]\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let mut out = Vec::new();
+        write_json(&mut out, &warnings()).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                {
+                    "path": "a.md",
+                    "line": 1,
+                    "column": 5,
+                    "end_line": 1,
+                    "end_column": 12,
+                    "message": "Weasel words",
+                    "rule_id": "weasel-words",
+                    "severity": "warning",
+                    "grade": null,
+                },
+                {
+                    "path": "b.md",
+                    "line": 3,
+                    "column": 1,
+                    "end_line": 4,
+                    "end_column": 2,
+                    "message": "Passive construction",
+                    "rule_id": "passive-construction",
+                    "severity": "info",
+                    "grade": null,
+                },
+                {
+                    "path": "b.md",
+                    "line": 5,
+                    "column": 1,
+                    "end_line": 5,
+                    "end_column": 80,
+                    "message": "Hard to read (grade 11.0)",
+                    "rule_id": "hard-sentences",
+                    "severity": "info",
+                    "grade": 11.0,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_empty_json() {
+        let mut out = Vec::new();
+        write_json(&mut out, &[]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "[]\n");
+    }
+}
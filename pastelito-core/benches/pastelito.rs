@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use pastelito_core::{
-    doc::{Document, Parser},
+    doc::{Document, Edit, Parser},
     parsers::{MarkdownParser, PlaintextParser},
     rule::RuleSet,
     Tagger,
@@ -86,11 +86,47 @@ fn benchmark_default_ruleset(c: &mut Criterion) {
     });
 }
 
+fn benchmark_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Incremental");
+
+    let parser = MarkdownParser::default();
+    let ruleset = RuleSet::default();
+
+    let doc = Document::new(&parser, BLOG_POST);
+    let results = ruleset.apply(&doc);
+    let num_words = count_words(&doc);
+
+    // Simulate typing a single character in the middle of the document.
+    let offset = BLOG_POST.len() / 2;
+    let offset = (offset..)
+        .find(|offset| BLOG_POST.is_char_boundary(*offset))
+        .unwrap();
+    let edited = format!("{}x{}", &BLOG_POST[..offset], &BLOG_POST[offset..]);
+    let edit = Edit::between(BLOG_POST, &edited);
+
+    group.throughput(Throughput::Elements(num_words as u64));
+    group.bench_function("Document::update", |b| {
+        b.iter(|| {
+            let (doc, changes) = doc.update(&parser, edit, &edited);
+            black_box((doc, changes));
+        })
+    });
+
+    let (updated, changes) = doc.update(&parser, edit, &edited);
+    group.bench_function("RuleSet::apply_incremental", |b| {
+        b.iter(|| {
+            let results = ruleset.apply_incremental(&updated, &changes, &results);
+            black_box(results);
+        })
+    });
+}
+
 criterion_group!(
     benches,
     benchmark_parse_markdown,
     benchmark_parse_plaintext,
     benchmark_tag,
-    benchmark_default_ruleset
+    benchmark_default_ruleset,
+    benchmark_update
 );
 criterion_main!(benches);
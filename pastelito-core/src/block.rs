@@ -2,7 +2,7 @@ use pastelito_model::Tag;
 #[allow(unused_imports)]
 use strum::VariantArray as _;
 
-use crate::{span::FullByteSpan, ByteSpan};
+use crate::{sentence::Sentences, span::FullByteSpan, ByteSpan};
 
 /// The "kind" of a block. This allows rules to change their behavior based on
 /// the kind of block.
@@ -10,8 +10,8 @@ use crate::{span::FullByteSpan, ByteSpan};
 pub enum BlockKind {
     /// A regular text paragraph
     Paragraph,
-    /// A heading or title
-    Heading,
+    /// A heading or title, with its level from 1 for a top-level heading to 6
+    Heading(u8),
 }
 
 /// A word in a block of text.
@@ -70,6 +70,15 @@ impl<'input> Word<'input> {
         self.tag
     }
 
+    /// Check if this word is tagged as a proper noun, e.g. "London" or
+    /// "Smiths".
+    pub fn is_proper_noun(&self) -> bool {
+        matches!(
+            self.tag,
+            Some(Tag::ProperNounSingular | Tag::ProperNounPlural)
+        )
+    }
+
     /// Set the part-of-speech tag of this word.
     pub fn set_tag(&mut self, tag: Tag) {
         self.tag = Some(tag);
@@ -143,6 +152,28 @@ impl<T> Block<T> {
     }
 }
 
+impl<'input> Block<Word<'input>> {
+    /// Get the span covering all the words in this block.
+    ///
+    /// Returns `None` if the block is empty.
+    pub fn as_span(&self) -> Option<ByteSpan> {
+        if self.contents.is_empty() {
+            None
+        } else {
+            Some(self.as_slice().into())
+        }
+    }
+
+    /// Get an iterator over the sentences in this block.
+    ///
+    /// Each sentence is a non-empty slice of the block's words. Use
+    /// `ByteSpan::from` to get the span of a sentence. Sentences are split
+    /// using the tags, so the block must be tagged first.
+    pub fn sentences(&self) -> Sentences<'_, 'input> {
+        Sentences::new(self.as_slice())
+    }
+}
+
 impl<T> IntoIterator for Block<T> {
     type Item = T;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -0,0 +1,821 @@
+//! Per-project configuration, loaded from `pastelito.toml` files.
+//!
+//! A config file chooses which built-in rules and measures are active, and
+//! overrides rule severities. For example:
+//!
+//! ```toml
+//! # Stop searching parent directories for more config files.
+//! root = true
+//!
+//! # Load more rules from rule files, relative to this config file.
+//! rule-files = ["docs/style.toml"]
+//!
+//! # Check spelling with Hunspell dictionaries, and a list of project words.
+//! dictionaries = ["/usr/share/hunspell/en_US.dic"]
+//! word-lists = ["words.txt"]
+//!
+//! # Check terms against project glossaries.
+//! glossaries = ["docs/glossary.toml"]
+//!
+//! [rules.weak-ing]
+//! enabled = false
+//!
+//! [rules.passive-construction]
+//! severity = "error"
+//!
+//! # Some rules have options.
+//! [rules.hard-sentences]
+//! hard-grade = 12
+//!
+//! [measures.prepositions]
+//! enabled = false
+//! ```
+//!
+//! Rules are identified by their id, e.g. `passive-construction`, and
+//! measures by their name, e.g. `be-verbs`. Rules from rule files can be
+//! configured in the same way as built-in rules.
+//!
+//! `Config::discover` searches for config files in the directory of a
+//! document and each of its parents, stopping at a config file with
+//! `root = true`. The config files are merged, with settings in a directory
+//! overriding settings from its parents.
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    dictionary::{Dictionary, DictionaryError},
+    glossary::{Glossary, GlossaryError},
+    measures::default_measures,
+    rule::{MeasureKey, RuleOptions, RuleSet, Severity},
+    rule_file::{self, RuleFileError},
+    rules::{default_rules, Spelling, Terminology},
+};
+
+/// The name of the config file.
+pub const CONFIG_FILE_NAME: &str = "pastelito.toml";
+
+/// An error when loading a config file, or building a `RuleSet` from it.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A config file could not be read.
+    Io(PathBuf, io::Error),
+    /// A config file is not valid TOML, or does not match the expected schema.
+    Toml(PathBuf, Box<toml::de::Error>),
+    /// A rule file referred to by a config file could not be loaded.
+    RuleFile(PathBuf, Box<RuleFileError>),
+    /// A dictionary or word list referred to by a config file could not be
+    /// loaded.
+    Dictionary(PathBuf, Box<DictionaryError>),
+    /// A glossary referred to by a config file could not be loaded.
+    Glossary(PathBuf, Box<GlossaryError>),
+    /// A config file refers to a rule id which does not exist.
+    UnknownRule(Option<PathBuf>, String),
+    /// A config file refers to a measure which does not exist.
+    UnknownMeasure(Option<PathBuf>, String),
+    /// Two rules have the same id.
+    DuplicateRule(String),
+    /// The options for a rule are not valid.
+    InvalidRuleOptions(Option<PathBuf>, String, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            ConfigError::Toml(path, err) => write!(f, "{}: {}", path.display(), err),
+            ConfigError::RuleFile(path, err) => write!(f, "{}: {}", path.display(), err),
+            ConfigError::Dictionary(path, err) => write!(f, "{}: {}", path.display(), err),
+            ConfigError::Glossary(path, err) => write!(f, "{}: {}", path.display(), err),
+            ConfigError::UnknownRule(path, id) => {
+                write_path(f, path)?;
+                write!(f, "unknown rule {:?}", id)
+            }
+            ConfigError::UnknownMeasure(path, name) => {
+                write_path(f, path)?;
+                write!(f, "unknown measure {:?}", name)
+            }
+            ConfigError::DuplicateRule(id) => write!(f, "duplicate rule id {:?}", id),
+            ConfigError::InvalidRuleOptions(path, id, message) => {
+                write_path(f, path)?;
+                write!(f, "invalid options for rule {:?}: {}", id, message)
+            }
+        }
+    }
+}
+
+/// Write the `path: ` prefix for an error, if the path is known.
+fn write_path(f: &mut fmt::Formatter<'_>, path: &Option<PathBuf>) -> fmt::Result {
+    match path {
+        Some(path) => write!(f, "{}: ", path.display()),
+        None => Ok(()),
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(_, err) => Some(err),
+            ConfigError::Toml(_, err) => Some(err.as_ref()),
+            ConfigError::RuleFile(_, err) => Some(err.as_ref()),
+            ConfigError::Dictionary(_, err) => Some(err.as_ref()),
+            ConfigError::Glossary(_, err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// The settings for a single rule.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RuleConfig {
+    /// Is the rule active? Rules are active by default.
+    pub enabled: Option<bool>,
+    /// Override the default severity of the rule.
+    pub severity: Option<Severity>,
+    /// Any other settings are options for the rule, which are checked by
+    /// `Rule::configure`.
+    #[serde(flatten)]
+    pub options: RuleOptions,
+}
+
+impl RuleConfig {
+    fn merge(&mut self, other: RuleConfig) {
+        self.enabled = other.enabled.or(self.enabled);
+        self.severity = other.severity.or(self.severity);
+        self.options.extend(other.options);
+    }
+}
+
+/// The settings for a single measure.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MeasureConfig {
+    /// Is the measure active? Measures are active by default.
+    pub enabled: Option<bool>,
+}
+
+impl MeasureConfig {
+    fn merge(&mut self, other: MeasureConfig) {
+        self.enabled = other.enabled.or(self.enabled);
+    }
+}
+
+/// The configuration for a project, from one or more config files.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// If true, do not search parent directories for more config files.
+    pub root: bool,
+    /// Rule files to load.
+    pub rule_files: Vec<PathBuf>,
+    /// Hunspell dictionaries for the `spelling` rule, as paths to their
+    /// `.dic` files. Each `.aff` file must be next to its `.dic` file.
+    pub dictionaries: Vec<PathBuf>,
+    /// Word lists for the `spelling` rule, with one correct word per line.
+    pub word_lists: Vec<PathBuf>,
+    /// Glossaries for the `terminology` rule.
+    pub glossaries: Vec<PathBuf>,
+    /// The settings for each rule, by rule id.
+    pub rules: BTreeMap<String, RuleConfig>,
+    /// The settings for each measure, by measure name.
+    pub measures: BTreeMap<String, MeasureConfig>,
+    /// The config files which were merged to create this config.
+    #[serde(skip)]
+    sources: Vec<PathBuf>,
+    /// The config file which last set each entry in `rules`, for errors.
+    #[serde(skip)]
+    rule_sources: BTreeMap<String, PathBuf>,
+    /// The config file which last set each entry in `measures`, for errors.
+    #[serde(skip)]
+    measure_sources: BTreeMap<String, PathBuf>,
+}
+
+impl Config {
+    /// Parse a config file.
+    ///
+    /// Relative paths in `rule-files`, `dictionaries`, `word-lists` and
+    /// `glossaries` are resolved relative to the directory containing `path`.
+    pub fn from_toml_str(input: &str, path: &Path) -> Result<Self, ConfigError> {
+        let mut config: Config = toml::from_str(input)
+            .map_err(|err| ConfigError::Toml(path.to_path_buf(), Box::new(err)))?;
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for file in config
+            .rule_files
+            .iter_mut()
+            .chain(config.dictionaries.iter_mut())
+            .chain(config.word_lists.iter_mut())
+            .chain(config.glossaries.iter_mut())
+        {
+            *file = dir.join(&*file);
+        }
+        config.sources = vec![path.to_path_buf()];
+        config.rule_sources = config
+            .rules
+            .keys()
+            .map(|id| (id.clone(), path.to_path_buf()))
+            .collect();
+        config.measure_sources = config
+            .measures
+            .keys()
+            .map(|name| (name.clone(), path.to_path_buf()))
+            .collect();
+
+        Ok(config)
+    }
+
+    /// Load a config file.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let input =
+            fs::read_to_string(path).map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
+        Self::from_toml_str(&input, path)
+    }
+
+    /// Find and merge the config files for a document at `path`.
+    ///
+    /// `path` may be a file or a directory. Each directory from `path` up to
+    /// the root of the filesystem is searched for a `pastelito.toml`, stopping
+    /// after a config file with `root = true`. If no config files are found,
+    /// the default config is returned.
+    pub fn discover(path: &Path) -> Result<Self, ConfigError> {
+        let dir = if path.is_dir() {
+            path
+        } else {
+            match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            }
+        };
+
+        // Use an absolute path so we can walk all the way up.
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+        let mut configs = Vec::new();
+        for ancestor in dir.ancestors() {
+            let candidate = ancestor.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                let config = Self::load(&candidate)?;
+                let root = config.root;
+                configs.push(config);
+                if root {
+                    break;
+                }
+            }
+        }
+
+        let mut merged = Config::default();
+        for config in configs.into_iter().rev() {
+            merged.merge(config);
+        }
+        Ok(merged)
+    }
+
+    /// Merge `other` into this config. Settings in `other` take precedence.
+    pub fn merge(&mut self, other: Config) {
+        self.root |= other.root;
+        self.rule_files.extend(other.rule_files);
+        self.dictionaries.extend(other.dictionaries);
+        self.word_lists.extend(other.word_lists);
+        self.glossaries.extend(other.glossaries);
+        for (id, rule) in other.rules {
+            self.rules.entry(id).or_default().merge(rule);
+        }
+        for (name, measure) in other.measures {
+            self.measures.entry(name).or_default().merge(measure);
+        }
+        self.sources.extend(other.sources);
+        self.rule_sources.extend(other.rule_sources);
+        self.measure_sources.extend(other.measure_sources);
+    }
+
+    /// The config files which were merged to create this config, from the
+    /// outermost directory to the innermost.
+    ///
+    /// Two configs with the same sources build the same `RuleSet`, so this can
+    /// be used to cache rule sets.
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+
+    /// Build a `RuleSet` from the built-in rules and measures, plus the rules
+    /// from any rule files.
+    ///
+    /// This also loads the dictionaries and word lists for the `spelling`
+    /// rule, and the glossaries for the `terminology` rule.
+    pub fn build_ruleset(&self) -> Result<RuleSet, ConfigError> {
+        let mut rules = default_rules();
+        rules.push(Box::new(self.load_spelling()?));
+        rules.push(Box::new(self.load_terminology()?));
+        for path in &self.rule_files {
+            let loaded = rule_file::from_path(path)
+                .map_err(|err| ConfigError::RuleFile(path.clone(), Box::new(err)))?;
+            rules.extend(loaded);
+        }
+
+        let mut ids = HashSet::new();
+        for rule in &rules {
+            if !ids.insert(rule.id().to_string()) {
+                return Err(ConfigError::DuplicateRule(rule.id().to_string()));
+            }
+        }
+
+        if let Some(id) = self.rules.keys().find(|id| !ids.contains(*id)) {
+            return Err(ConfigError::UnknownRule(
+                self.rule_sources.get(id).cloned(),
+                id.clone(),
+            ));
+        }
+
+        if let Some(name) = self
+            .measures
+            .keys()
+            .find(|name| MeasureKey::from_str(name).is_err())
+        {
+            return Err(ConfigError::UnknownMeasure(
+                self.measure_sources.get(name).cloned(),
+                name.clone(),
+            ));
+        }
+
+        let measures = default_measures()
+            .into_iter()
+            .filter(|measure| {
+                self.measures
+                    .get(measure.key().as_str())
+                    .and_then(|config| config.enabled)
+                    .unwrap_or(true)
+            })
+            .collect();
+
+        let mut ruleset = RuleSet::new(Vec::new(), measures);
+        for mut rule in rules {
+            let Some(config) = self.rules.get(rule.id()) else {
+                if rule.is_enabled_by_default() {
+                    let severity = rule.default_severity();
+                    ruleset.add_rule(rule, severity);
+                }
+                continue;
+            };
+
+            rule.configure(&config.options).map_err(|message| {
+                ConfigError::InvalidRuleOptions(
+                    self.rule_sources.get(rule.id()).cloned(),
+                    rule.id().to_string(),
+                    message,
+                )
+            })?;
+
+            if config.enabled.unwrap_or(rule.is_enabled_by_default()) {
+                let severity = config.severity.unwrap_or(rule.default_severity());
+                ruleset.add_rule(rule, severity);
+            }
+        }
+
+        Ok(ruleset)
+    }
+
+    /// Create the `spelling` rule from the dictionaries and word lists.
+    fn load_spelling(&self) -> Result<Spelling, ConfigError> {
+        let load = |paths: &[PathBuf], load: fn(&Path) -> Result<Dictionary, DictionaryError>| {
+            paths
+                .iter()
+                .map(|path| {
+                    load(path).map_err(|err| ConfigError::Dictionary(path.clone(), Box::new(err)))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Spelling::new(
+            load(&self.dictionaries, Dictionary::load)?,
+            load(&self.word_lists, Dictionary::load_word_list)?,
+        ))
+    }
+
+    /// Create the `terminology` rule from the glossaries.
+    fn load_terminology(&self) -> Result<Terminology, ConfigError> {
+        let glossaries = self
+            .glossaries
+            .iter()
+            .map(|path| {
+                Glossary::load(path)
+                    .map_err(|err| ConfigError::Glossary(path.clone(), Box::new(err)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Terminology::new(glossaries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use crate::{
+        doc::Document,
+        parsers::{MarkdownParser, PlaintextParser},
+        rule::{MeasureKey, RuleOptions, Severity},
+    };
+
+    use super::{Config, ConfigError, MeasureConfig, RuleConfig};
+
+    fn parse(input: &str) -> Config {
+        Config::from_toml_str(input, Path::new("/project/pastelito.toml")).unwrap()
+    }
+
+    /// Create a directory tree of `files` in a temporary directory, and call
+    /// `f` with the path to the directory.
+    fn with_tree(name: &str, files: &[(&str, &str)], f: impl FnOnce(&Path)) {
+        let root =
+            std::env::temp_dir().join(format!("pastelito-config-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for (path, contents) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        f(&root);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    /// The rule ids and severities of the warnings from applying `config` to
+    /// `input`.
+    fn warnings(config: &Config, input: &str) -> Vec<(String, Severity)> {
+        let doc = Document::new(&PlaintextParser::default(), input);
+        let ruleset = config.build_ruleset().unwrap();
+        let results = ruleset.apply(&doc);
+        results
+            .iter_warnings()
+            .map(|warning| (warning.rule_id.clone(), warning.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = parse(
+            r#"
+            root = true
+            rule-files = ["style.toml", "/abs/rules.yaml"]
+
+            [rules.weak-ing]
+            enabled = false
+
+            [rules.passive-construction]
+            severity = "error"
+
+            [measures.prepositions]
+            enabled = false
+            "#,
+        );
+
+        assert!(config.root);
+        assert_eq!(
+            config.rule_files,
+            vec![
+                Path::new("/project/style.toml"),
+                Path::new("/abs/rules.yaml")
+            ]
+        );
+        assert_eq!(
+            config.rules["weak-ing"],
+            RuleConfig {
+                enabled: Some(false),
+                severity: None,
+                options: RuleOptions::new(),
+            }
+        );
+        assert_eq!(
+            config.rules["passive-construction"].severity,
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            config.measures["prepositions"],
+            MeasureConfig {
+                enabled: Some(false)
+            }
+        );
+        assert_eq!(config.sources(), [Path::new("/project/pastelito.toml")]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let path = Path::new("pastelito.toml");
+        assert!(matches!(
+            Config::from_toml_str("[rules.weak-ing]\nseverity = \"fatal\"", path),
+            Err(ConfigError::Toml(_, _))
+        ));
+        assert!(matches!(
+            Config::from_toml_str("unknown = 1", path),
+            Err(ConfigError::Toml(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut config = parse(
+            r#"
+            [rules.weak-ing]
+            enabled = false
+            severity = "hint"
+
+            [rules.weasel-words]
+            severity = "error"
+            "#,
+        );
+        config.merge(parse(
+            r#"
+            [rules.weak-ing]
+            enabled = true
+
+            [measures.be-verbs]
+            enabled = false
+            "#,
+        ));
+
+        assert_eq!(
+            config.rules["weak-ing"],
+            RuleConfig {
+                enabled: Some(true),
+                severity: Some(Severity::Hint),
+                options: RuleOptions::new(),
+            }
+        );
+        assert_eq!(config.rules["weasel-words"].severity, Some(Severity::Error));
+        assert_eq!(config.measures["be-verbs"].enabled, Some(false));
+        assert_eq!(config.sources().len(), 2);
+    }
+
+    #[test]
+    fn test_build_ruleset() {
+        let input = "It is is quite good.";
+
+        assert_eq!(
+            warnings(&Config::default(), input),
+            vec![
+                ("repeated-words".to_string(), Severity::Error),
+                ("weasel-words".to_string(), Severity::Warning),
+            ]
+        );
+
+        let config = parse(
+            r#"
+            [rules.repeated-words]
+            enabled = false
+
+            [rules.weasel-words]
+            severity = "hint"
+            "#,
+        );
+        assert_eq!(
+            warnings(&config, input),
+            vec![("weasel-words".to_string(), Severity::Hint)]
+        );
+    }
+
+    #[test]
+    fn test_build_disabled_by_default() {
+        let doc = Document::new(&MarkdownParser::default(), "# Getting Started");
+        let rule_ids = |config: Config| {
+            let results = config.build_ruleset().unwrap().apply(&doc);
+            results
+                .iter_warnings()
+                .map(|warning| warning.rule_id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(rule_ids(Config::default()), Vec::<String>::new());
+        assert_eq!(
+            rule_ids(parse("[rules.heading-style]\ncase = \"sentence\"")),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            rule_ids(parse("[rules.heading-style]\nenabled = true")),
+            vec!["heading-style"]
+        );
+    }
+
+    #[test]
+    fn test_build_measures() {
+        let config = parse("[measures.be-verbs]\nenabled = false");
+        let doc = Document::new(&PlaintextParser::default(), "It is on the mat.");
+        let results = config.build_ruleset().unwrap().apply(&doc);
+        let keys = results
+            .iter_measurements()
+            .map(|measurement| measurement.key)
+            .collect::<Vec<_>>();
+
+        assert!(!keys.contains(&MeasureKey::BeVerbs));
+        assert!(keys.contains(&MeasureKey::Prepositions));
+    }
+
+    #[test]
+    fn test_build_errors() {
+        assert!(matches!(
+            parse("[rules.no-such-rule]\nenabled = false").build_ruleset(),
+            Err(ConfigError::UnknownRule(Some(_), id)) if id == "no-such-rule"
+        ));
+        assert!(matches!(
+            parse("[measures.adverbs]\nenabled = false").build_ruleset(),
+            Err(ConfigError::UnknownMeasure(Some(_), name)) if name == "adverbs"
+        ));
+
+        let mut config = parse("[rules.weak-ing]\nenabled = false");
+        config.merge(
+            Config::from_toml_str(
+                "[rules.typo]\nenabled = false",
+                Path::new("/project/docs/pastelito.toml"),
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            config.build_ruleset().err().unwrap().to_string(),
+            "/project/docs/pastelito.toml: unknown rule \"typo\""
+        );
+    }
+
+    #[test]
+    fn test_rule_options() {
+        let mut config = parse(
+            r#"
+            [rules.hard-sentences]
+            hard-grade = 8
+            very-hard-grade = 12
+            "#,
+        );
+        config.merge(parse(
+            "[rules.hard-sentences]
+hard-grade = 9.5",
+        ));
+        assert_eq!(
+            config.rules["hard-sentences"].options,
+            toml::toml! {
+                hard-grade = 9.5
+                very-hard-grade = 12
+            }
+        );
+        assert!(config.build_ruleset().is_ok());
+
+        assert_eq!(
+            parse("[rules.weak-ing]
+max = 1")
+                .build_ruleset()
+                .err()
+                .unwrap()
+                .to_string(),
+            "/project/pastelito.toml: invalid options for rule \"weak-ing\": unknown option \"max\""
+        );
+        assert!(matches!(
+            parse("[rules.hard-sentences]
+        hard-grade = \"high\"").build_ruleset(),
+            Err(ConfigError::InvalidRuleOptions(Some(_), id, _)) if id == "hard-sentences"
+        ));
+    }
+
+    #[test]
+    fn test_spelling() {
+        with_tree(
+            "spelling",
+            &[
+                (
+                    "pastelito.toml",
+                    "dictionaries = [\"dict/en.dic\"]\nword-lists = [\"words.txt\"]\n\n\
+                     [rules.spelling]\nwords = [\"pastelito\"]",
+                ),
+                ("dict/en.aff", "SFX S Y 1\nSFX S 0 s .\n"),
+                ("dict/en.dic", "3\nit\nis\ncat/S\n"),
+                ("words.txt", "vscode\n"),
+                ("missing/pastelito.toml", "dictionaries = [\"en.dic\"]"),
+            ],
+            |root| {
+                // The text of each spelling warning.
+                let spelling = |config: &Config, input: &'static str| {
+                    let doc = Document::new(&PlaintextParser::default(), input);
+                    let results = config.build_ruleset().unwrap().apply(&doc);
+                    results
+                        .iter_warnings()
+                        .filter(|warning| warning.rule_id == "spelling")
+                        .map(|warning| warning.span.as_str(input))
+                        .collect::<Vec<_>>()
+                };
+
+                let config = Config::discover(root).unwrap();
+                assert_eq!(config.dictionaries, vec![root.join("dict/en.dic")]);
+                assert_eq!(config.word_lists, vec![root.join("words.txt")]);
+                assert_eq!(
+                    spelling(&config, "It is cats, vscode and pastelito. It is kats."),
+                    vec!["and", "kats"]
+                );
+
+                // Without a dictionary, spelling is not checked.
+                assert!(spelling(&Config::default(), "It is kats.").is_empty());
+
+                let config = Config::discover(&root.join("missing")).unwrap();
+                assert!(matches!(
+                    config.build_ruleset(),
+                    Err(ConfigError::Dictionary(path, _)) if path.ends_with("missing/en.dic")
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_glossaries() {
+        with_tree(
+            "glossaries",
+            &[
+                (
+                    "pastelito.toml",
+                    "glossaries = [\"glossary.toml\"]\n\n\
+                     [[rules.terminology.terms]]\nterm = \"email\"\nvariants = [\"e-mail\"]",
+                ),
+                ("glossary.toml", "[[terms]]\nterm = \"GitHub\""),
+                ("invalid/pastelito.toml", "glossaries = [\"glossary.toml\"]"),
+                ("invalid/glossary.toml", "[[terms]]\nterm = \"\""),
+            ],
+            |root| {
+                // The text of each terminology warning.
+                let terminology = |config: &Config, input: &'static str| {
+                    let doc = Document::new(&PlaintextParser::default(), input);
+                    let results = config.build_ruleset().unwrap().apply(&doc);
+                    results
+                        .iter_warnings()
+                        .filter(|warning| warning.rule_id == "terminology")
+                        .map(|warning| warning.span.as_str(input))
+                        .collect::<Vec<_>>()
+                };
+
+                let config = Config::discover(root).unwrap();
+                assert_eq!(config.glossaries, vec![root.join("glossary.toml")]);
+                assert_eq!(
+                    terminology(&config, "Send an e-mail about Github."),
+                    vec!["e-mail", "Github"]
+                );
+                assert!(terminology(&Config::default(), "Send an e-mail.").is_empty());
+
+                let config = Config::discover(&root.join("invalid")).unwrap();
+                assert!(matches!(
+                    config.build_ruleset(),
+                    Err(ConfigError::Glossary(path, _)) if path.ends_with("invalid/glossary.toml")
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn test_discover() {
+        with_tree(
+            "discover",
+            &[
+                (
+                    "pastelito.toml",
+                    "[rules.weasel-words]\nseverity = \"error\"\n\n[rules.repeated-words]\nenabled = false",
+                ),
+                (
+                    "docs/pastelito.toml",
+                    "rule-files = [\"style.toml\"]\n\n[rules.weasel-words]\nseverity = \"info\"",
+                ),
+                (
+                    "docs/style.toml",
+                    "[[rules]]\nid = \"utilize\"\nmessage = \"Use 'use'\"\npattern = [\"utilize\"]",
+                ),
+                ("docs/guide.md", ""),
+                ("other/notes.md", ""),
+                ("standalone/pastelito.toml", "root = true"),
+                ("standalone/notes.md", ""),
+            ],
+            |root| {
+                let input = "It is is quite good. We utilize it.";
+
+                let config = Config::discover(&root.join("docs/guide.md")).unwrap();
+                assert_eq!(
+                    warnings(&config, input),
+                    vec![
+                        ("weasel-words".to_string(), Severity::Info),
+                        ("utilize".to_string(), Severity::Warning),
+                    ]
+                );
+
+                let config = Config::discover(&root.join("other/notes.md")).unwrap();
+                assert_eq!(
+                    warnings(&config, input),
+                    vec![("weasel-words".to_string(), Severity::Error)]
+                );
+
+                let config = Config::discover(&root.join("standalone/notes.md")).unwrap();
+                assert_eq!(config.sources().len(), 1);
+                assert_eq!(
+                    warnings(&config, input),
+                    vec![
+                        ("repeated-words".to_string(), Severity::Error),
+                        ("weasel-words".to_string(), Severity::Warning),
+                    ]
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,988 @@
+//! Spelling dictionaries in the Hunspell format.
+//!
+//! A Hunspell dictionary is a pair of files. The `.dic` file lists the words,
+//! each with optional affix flags, e.g. `walk/DGS`. The `.aff` file defines
+//! the prefixes and suffixes for each flag, e.g. `D` adds "ed" to "walk", and
+//! other settings used for spelling suggestions.
+//!
+//! Only a subset of the format is supported: the `SET`, `FLAG`, `TRY`, `REP`,
+//! `PFX`, `SFX`, `KEEPCASE`, `NEEDAFFIX`, `FORBIDDENWORD` and `NOSUGGEST`
+//! directives. Prefixes and suffixes can be combined if both allow it, but
+//! compound words and affixes with their own affixes (twofold suffixes) are
+//! not supported. Other directives are ignored. This is enough for common
+//! dictionaries such as `en_US`.
+//!
+//! Dictionaries can also be loaded from a word list, with one word per line.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// An affix flag. Flags are stored as numbers, whichever `FLAG` format is
+/// used.
+type Flag = u32;
+
+/// An error when loading a dictionary.
+#[derive(Debug)]
+pub enum DictionaryError {
+    /// A file could not be read.
+    Io(PathBuf, io::Error),
+    /// The `.aff` file uses a character encoding other than UTF-8 or
+    /// ISO-8859-1.
+    UnsupportedEncoding(String),
+    /// A file is not valid in its encoding.
+    InvalidEncoding(PathBuf),
+    /// A line in the `.aff` file is not valid.
+    InvalidAff {
+        /// The line number, starting from 1.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+    /// A line in the `.dic` file is not valid.
+    InvalidDic {
+        /// The line number, starting from 1.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DictionaryError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            DictionaryError::UnsupportedEncoding(encoding) => {
+                write!(f, "unsupported encoding {:?}", encoding)
+            }
+            DictionaryError::InvalidEncoding(path) => {
+                write!(f, "{}: invalid character encoding", path.display())
+            }
+            DictionaryError::InvalidAff { line, message } => {
+                write!(f, ".aff line {}: {}", line, message)
+            }
+            DictionaryError::InvalidDic { line, message } => {
+                write!(f, ".dic line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DictionaryError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// How flags are written in the `.aff` and `.dic` files.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum FlagFormat {
+    /// Each flag is a single character, e.g. `walk/DGS`. This is the default,
+    /// and is also used for `FLAG UTF-8`.
+    #[default]
+    Char,
+    /// Each flag is two characters, e.g. `walk/AaBb`.
+    Long,
+    /// Flags are comma-separated numbers, e.g. `walk/1,2,3`.
+    Num,
+}
+
+impl FlagFormat {
+    /// Parse a list of flags.
+    fn parse(self, flags: &str) -> Result<Vec<Flag>, String> {
+        match self {
+            FlagFormat::Char => Ok(flags.chars().map(Flag::from).collect()),
+            FlagFormat::Long => {
+                let chars = flags.chars().collect::<Vec<_>>();
+                if chars.len() % 2 != 0 {
+                    return Err(format!(
+                        "odd number of characters in long flags {:?}",
+                        flags
+                    ));
+                }
+                Ok(chars
+                    .chunks(2)
+                    .map(|pair| (Flag::from(pair[0]) << 16) | Flag::from(pair[1]))
+                    .collect())
+            }
+            FlagFormat::Num => flags
+                .split(',')
+                .map(|flag| {
+                    flag.trim()
+                        .parse()
+                        .map_err(|_| format!("invalid numeric flag {:?}", flag))
+                })
+                .collect(),
+        }
+    }
+
+    /// Parse a single flag.
+    fn parse_one(self, flag: &str) -> Result<Flag, String> {
+        match self.parse(flag)?.as_slice() {
+            [flag] => Ok(*flag),
+            _ => Err(format!("expected a single flag, found {:?}", flag)),
+        }
+    }
+}
+
+/// A part of an affix condition, which matches a single character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ConditionPart {
+    /// `.`, which matches any character.
+    Any,
+    /// A character, or a set of characters such as `[aeiou]`, which matches
+    /// any of the characters. If `negated`, e.g. `[^aeiou]`, it matches any
+    /// other character.
+    Chars { chars: Vec<char>, negated: bool },
+}
+
+impl ConditionPart {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ConditionPart::Any => true,
+            ConditionPart::Chars { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// The condition of an affix, which the stem must match at its start (for a
+/// prefix) or its end (for a suffix), e.g. `[^aeiou]y`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Condition {
+    parts: Vec<ConditionPart>,
+}
+
+impl Condition {
+    fn parse(condition: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut chars = condition.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => parts.push(ConditionPart::Any),
+                '[' => {
+                    let mut set = Vec::new();
+                    let mut negated = false;
+                    loop {
+                        match chars.next() {
+                            Some('^') if set.is_empty() && !negated => negated = true,
+                            Some(']') => break,
+                            Some(c) => set.push(c),
+                            None => return Err(format!("unclosed '[' in {:?}", condition)),
+                        }
+                    }
+                    parts.push(ConditionPart::Chars {
+                        chars: set,
+                        negated,
+                    });
+                }
+                c => parts.push(ConditionPart::Chars {
+                    chars: vec![c],
+                    negated: false,
+                }),
+            }
+        }
+
+        // "." matches any stem, so skip it.
+        if parts == [ConditionPart::Any] {
+            parts.clear();
+        }
+        Ok(Condition { parts })
+    }
+
+    /// Does `stem` start with this condition?
+    fn matches_start(&self, stem: &str) -> bool {
+        let mut chars = stem.chars();
+        self.parts
+            .iter()
+            .all(|part| chars.next().is_some_and(|c| part.matches(c)))
+    }
+
+    /// Does `stem` end with this condition?
+    fn matches_end(&self, stem: &str) -> bool {
+        let mut chars = stem.chars().rev();
+        self.parts
+            .iter()
+            .rev()
+            .all(|part| chars.next().is_some_and(|c| part.matches(c)))
+    }
+}
+
+/// A prefix or suffix rule, e.g. `SFX D y ied [^aeiou]y`, which turns "fly"
+/// into "flied".
+#[derive(Clone, Debug)]
+struct Affix {
+    /// The flag which a word must have to use this affix.
+    flag: Flag,
+    /// Can this affix be combined with an affix of the other kind?
+    cross_product: bool,
+    /// The text removed from the stem before adding the affix.
+    strip: String,
+    /// The condition the stem must match.
+    condition: Condition,
+}
+
+/// The affixes of one kind, indexed by the text they add.
+#[derive(Clone, Debug, Default)]
+struct Affixes {
+    by_add: HashMap<String, Vec<Affix>>,
+}
+
+impl Affixes {
+    fn insert(&mut self, add: String, affix: Affix) {
+        self.by_add.entry(add).or_default().push(affix);
+    }
+}
+
+/// The settings from a `.aff` file.
+#[derive(Clone, Debug, Default)]
+struct Aff {
+    flag_format: FlagFormat,
+    prefixes: Affixes,
+    suffixes: Affixes,
+    /// The characters to try when suggesting edits, most common first.
+    try_chars: Vec<char>,
+    /// Common mistakes, and their replacements, used for suggestions.
+    replacements: Vec<(String, String)>,
+    /// Words with this flag must be written in the same case as in the
+    /// dictionary.
+    keep_case: Option<Flag>,
+    /// Words with this flag are only valid with an affix.
+    need_affix: Option<Flag>,
+    /// Words with this flag are always misspelled.
+    forbidden_word: Option<Flag>,
+    /// Words with this flag are never suggested.
+    no_suggest: Option<Flag>,
+}
+
+/// The affixes in a `PFX` or `SFX` block which have not been read yet.
+struct PendingAffixes {
+    cross_product: bool,
+    remaining: usize,
+}
+
+impl Aff {
+    fn parse(input: &str) -> Result<Self, DictionaryError> {
+        let mut aff = Aff::default();
+        let mut pending: HashMap<(String, Flag), PendingAffixes> = HashMap::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let invalid = |message: String| DictionaryError::InvalidAff {
+                line: i + 1,
+                message,
+            };
+
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let Some(&directive) = fields.first() else {
+                continue;
+            };
+            let arg = || {
+                fields
+                    .get(1)
+                    .copied()
+                    .ok_or_else(|| invalid(format!("missing argument to {}", directive)))
+            };
+
+            match directive {
+                "FLAG" => {
+                    aff.flag_format = match arg()? {
+                        "long" => FlagFormat::Long,
+                        "num" => FlagFormat::Num,
+                        "UTF-8" => FlagFormat::Char,
+                        format => return Err(invalid(format!("unknown flag format {:?}", format))),
+                    }
+                }
+                "TRY" => aff.try_chars = arg()?.chars().collect(),
+                "REP" => {
+                    // The first REP line is the number of replacements.
+                    if let [_, from, to, ..] = fields.as_slice() {
+                        aff.replacements.push((
+                            from.trim_matches(['^', '$']).replace('_', " "),
+                            to.trim_matches(['^', '$']).replace('_', " "),
+                        ));
+                    }
+                }
+                "KEEPCASE" => aff.keep_case = Some(aff.parse_flag(arg()?).map_err(invalid)?),
+                "NEEDAFFIX" | "PSEUDOROOT" => {
+                    aff.need_affix = Some(aff.parse_flag(arg()?).map_err(invalid)?)
+                }
+                "FORBIDDENWORD" => {
+                    aff.forbidden_word = Some(aff.parse_flag(arg()?).map_err(invalid)?)
+                }
+                "NOSUGGEST" => aff.no_suggest = Some(aff.parse_flag(arg()?).map_err(invalid)?),
+                "PFX" | "SFX" => {
+                    let flag = aff.parse_flag(arg()?).map_err(invalid)?;
+                    let key = (directive.to_string(), flag);
+                    match pending.get_mut(&key) {
+                        Some(block) if block.remaining > 0 => {
+                            block.remaining -= 1;
+                            let cross_product = block.cross_product;
+                            aff.parse_affix(directive, flag, cross_product, &fields)
+                                .map_err(invalid)?;
+                        }
+                        _ => {
+                            // The header of a block, e.g. `SFX D Y 4`.
+                            let [_, _, cross_product, count, ..] = fields.as_slice() else {
+                                return Err(invalid(format!("invalid {} header", directive)));
+                            };
+                            let remaining = count
+                                .parse()
+                                .map_err(|_| invalid(format!("invalid count {:?}", count)))?;
+                            pending.insert(
+                                key,
+                                PendingAffixes {
+                                    cross_product: *cross_product == "Y",
+                                    remaining,
+                                },
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(aff)
+    }
+
+    fn parse_flag(&self, flag: &str) -> Result<Flag, String> {
+        self.flag_format.parse_one(flag)
+    }
+
+    /// Parse an affix line, e.g. `SFX D y ied [^aeiou]y`.
+    fn parse_affix(
+        &mut self,
+        directive: &str,
+        flag: Flag,
+        cross_product: bool,
+        fields: &[&str],
+    ) -> Result<(), String> {
+        let [_, _, strip, add, rest @ ..] = fields else {
+            return Err(format!("invalid {} line", directive));
+        };
+        let strip = if *strip == "0" { "" } else { strip };
+        // Any continuation flags after the "/" are not supported.
+        let add = add.split('/').next().unwrap_or_default();
+        let add = if add == "0" { "" } else { add };
+        let condition = Condition::parse(rest.first().copied().unwrap_or("."))?;
+
+        let affix = Affix {
+            flag,
+            cross_product,
+            strip: strip.to_string(),
+            condition,
+        };
+        if directive == "PFX" {
+            self.prefixes.insert(add.to_string(), affix);
+        } else {
+            self.suffixes.insert(add.to_string(), affix);
+        }
+        Ok(())
+    }
+}
+
+/// The case of a word.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Case {
+    /// No uppercase letters, e.g. "cat".
+    Lower,
+    /// An uppercase first letter and no others, e.g. "Cat".
+    Capitalized,
+    /// Only uppercase letters, e.g. "CAT".
+    Upper,
+    /// Any other mix, e.g. "iPhone".
+    Mixed,
+}
+
+impl Case {
+    fn of(word: &str) -> Case {
+        let mut letters = word.chars().filter(|c| c.is_alphabetic());
+        let Some(first) = letters.next() else {
+            return Case::Lower;
+        };
+        let (upper, lower) = letters.fold((0, 0), |(upper, lower), c| {
+            (
+                upper + c.is_uppercase() as usize,
+                lower + c.is_lowercase() as usize,
+            )
+        });
+        match (first.is_uppercase(), upper, lower) {
+            (false, 0, _) => Case::Lower,
+            (true, 0, _) => Case::Capitalized,
+            (true, _, 0) => Case::Upper,
+            _ => Case::Mixed,
+        }
+    }
+}
+
+/// Uppercase the first letter of `word`, and lowercase the rest.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// The kinds of suggestions, from the most to the least likely.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SuggestionKind {
+    /// The word in a different case, e.g. "paris" to "Paris".
+    Case,
+    /// A common mistake from the `REP` table, e.g. "fone" to "phone".
+    Replacement,
+    /// Two adjacent characters swapped, e.g. "teh" to "the".
+    Swap,
+    /// An extra character removed, e.g. "catt" to "cat".
+    Delete,
+    /// A missing character added, e.g. "ct" to "cat".
+    Insert,
+    /// A character replaced, e.g. "cot" to "cat".
+    Replace,
+    /// Two words run together, e.g. "thecat" to "the cat".
+    Split,
+}
+
+/// A spelling dictionary.
+#[derive(Clone, Debug, Default)]
+pub struct Dictionary {
+    aff: Aff,
+    /// Each word, and the flags of each of its entries.
+    words: HashMap<String, Vec<Box<[Flag]>>>,
+}
+
+impl Dictionary {
+    /// Load a Hunspell dictionary from its `.dic` file, and the `.aff` file
+    /// with the same name.
+    pub fn load(dic_path: &Path) -> Result<Self, DictionaryError> {
+        let aff_path = dic_path.with_extension("aff");
+        let aff_bytes = read(&aff_path)?;
+
+        // The encoding of both files is set by the `.aff` file.
+        let encoding = String::from_utf8_lossy(&aff_bytes)
+            .lines()
+            .find_map(|line| line.strip_prefix("SET "))
+            .map(|encoding| encoding.trim().to_string());
+        let decode = |bytes: Vec<u8>, path: &Path| match encoding.as_deref() {
+            None | Some("UTF-8") => {
+                String::from_utf8(bytes).map_err(|_| DictionaryError::InvalidEncoding(path.into()))
+            }
+            Some("ISO8859-1") | Some("ISO-8859-1") => {
+                Ok(bytes.into_iter().map(char::from).collect())
+            }
+            Some(encoding) => Err(DictionaryError::UnsupportedEncoding(encoding.into())),
+        };
+
+        let aff = decode(aff_bytes, &aff_path)?;
+        let dic = decode(read(dic_path)?, dic_path)?;
+        Self::new(&aff, &dic)
+    }
+
+    /// Create a dictionary from the contents of its `.aff` and `.dic` files.
+    pub fn new(aff: &str, dic: &str) -> Result<Self, DictionaryError> {
+        let mut dictionary = Dictionary {
+            aff: Aff::parse(aff.trim_start_matches('\u{feff}'))?,
+            words: HashMap::new(),
+        };
+
+        let mut lines = dic.trim_start_matches('\u{feff}').lines().enumerate();
+        // The first line is the approximate number of words.
+        lines.next();
+
+        for (i, line) in lines {
+            // Anything after whitespace is morphological data.
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            let (word, flags) = split_entry(entry);
+            let flags = match flags {
+                Some(flags) => dictionary.aff.flag_format.parse(flags).map_err(|message| {
+                    DictionaryError::InvalidDic {
+                        line: i + 1,
+                        message,
+                    }
+                })?,
+                None => Vec::new(),
+            };
+            dictionary
+                .words
+                .entry(word)
+                .or_default()
+                .push(flags.into_boxed_slice());
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Load a word list, with one word per line.
+    ///
+    /// Blank lines, and lines starting with `#`, are ignored.
+    pub fn load_word_list(path: &Path) -> Result<Self, DictionaryError> {
+        let input = String::from_utf8(read(path)?)
+            .map_err(|_| DictionaryError::InvalidEncoding(path.into()))?;
+        let mut dictionary = Dictionary::default();
+        for line in input.lines() {
+            let word = line.trim();
+            if !word.is_empty() && !word.starts_with('#') {
+                dictionary.add_word(word);
+            }
+        }
+        Ok(dictionary)
+    }
+
+    /// Add a word, without any affixes.
+    ///
+    /// Like the words in a `.dic` file, a lowercase word also allows the
+    /// capitalized and uppercase forms of the word.
+    pub fn add_word(&mut self, word: &str) {
+        self.words
+            .entry(word.to_string())
+            .or_default()
+            .push(Box::new([]));
+    }
+
+    /// Is the dictionary empty?
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Is `word` spelled correctly?
+    pub fn check(&self, word: &str) -> bool {
+        if self.is_forbidden(word) {
+            return false;
+        }
+        if self.check_form(word, true) {
+            return true;
+        }
+
+        // A word in the dictionary can also be capitalized, e.g. at the start
+        // of a sentence, or uppercase, unless it has the `KEEPCASE` flag.
+        match Case::of(word) {
+            Case::Capitalized => self.check_form(&word.to_lowercase(), false),
+            Case::Upper => {
+                self.check_form(&word.to_lowercase(), false)
+                    || self.check_form(&capitalize(word), false)
+            }
+            Case::Lower | Case::Mixed => false,
+        }
+    }
+
+    /// Suggest up to `max` corrections for a misspelled `word`, most likely
+    /// first. There are no suggestions for a correctly spelled word.
+    ///
+    /// The suggestions are words with a different case, replacements from
+    /// the dictionary's `REP` table, words with a single edit, and pairs of
+    /// words.
+    pub fn suggest(&self, word: &str, max: usize) -> Vec<String> {
+        if self.check(word) {
+            return Vec::new();
+        }
+
+        let mut candidates = Vec::new();
+        self.candidates(word, |kind, candidate| candidates.push((kind, candidate)));
+
+        // Prefer edits which keep the first letter, as it is rarely wrong.
+        let first = word.chars().next();
+        candidates.sort_by_key(|(kind, candidate)| (*kind, candidate.chars().next() != first));
+
+        let mut seen = HashSet::new();
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .filter(|candidate| candidate != word && seen.insert(candidate.clone()))
+            .filter(|candidate| self.is_suggestible(candidate))
+            .take(max)
+            .collect()
+    }
+
+    /// Generate the candidate suggestions for `word`, in no particular order.
+    fn candidates(&self, word: &str, mut add: impl FnMut(SuggestionKind, String)) {
+        let chars = word.chars().collect::<Vec<_>>();
+        let edit = |i: usize, replacement: &[char], len: usize| -> String {
+            chars[..i]
+                .iter()
+                .chain(replacement)
+                .chain(&chars[i + len..])
+                .collect()
+        };
+
+        add(SuggestionKind::Case, word.to_lowercase());
+        add(SuggestionKind::Case, capitalize(word));
+
+        for (from, to) in &self.aff.replacements {
+            for (i, _) in word.match_indices(from.as_str()) {
+                let candidate = format!("{}{}{}", &word[..i], to, &word[i + from.len()..]);
+                add(SuggestionKind::Replacement, candidate);
+            }
+        }
+
+        for i in 0..chars.len() {
+            if i + 1 < chars.len() {
+                add(SuggestionKind::Swap, edit(i, &[chars[i + 1], chars[i]], 2));
+            }
+            add(SuggestionKind::Delete, edit(i, &[], 1));
+        }
+
+        for &c in &self.aff.try_chars {
+            for i in 0..=chars.len() {
+                add(SuggestionKind::Insert, edit(i, &[c], 0));
+                if i < chars.len() && chars[i] != c {
+                    add(SuggestionKind::Replace, edit(i, &[c], 1));
+                }
+            }
+        }
+
+        for i in 1..chars.len() {
+            add(SuggestionKind::Split, edit(i, &[' '], 0));
+        }
+    }
+
+    /// Is `candidate` a correctly spelled word, or words, which can be
+    /// suggested?
+    fn is_suggestible(&self, candidate: &str) -> bool {
+        candidate.split(' ').all(|word| {
+            !word.is_empty()
+                && self.check(word)
+                && !self
+                    .entries(word)
+                    .any(|flags| self.has_flag(flags, self.aff.no_suggest))
+        })
+    }
+
+    /// Does `word` have an entry with the `FORBIDDENWORD` flag?
+    fn is_forbidden(&self, word: &str) -> bool {
+        self.entries(word)
+            .any(|flags| self.has_flag(flags, self.aff.forbidden_word))
+    }
+
+    /// The flags of each entry for `word`.
+    fn entries(&self, word: &str) -> impl Iterator<Item = &[Flag]> {
+        self.words
+            .get(word)
+            .into_iter()
+            .flatten()
+            .map(|flags| &**flags)
+    }
+
+    fn has_flag(&self, flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|flag| flags.contains(&flag))
+    }
+
+    /// Can `flags` be used for a word which is written as `is_exact` (in the
+    /// same case as the dictionary), or not?
+    fn allows_case(&self, flags: &[Flag], is_exact: bool) -> bool {
+        is_exact || !self.has_flag(flags, self.aff.keep_case)
+    }
+
+    /// Is there an entry for `stem` with all of `affix_flags`?
+    fn has_stem(&self, stem: &str, affix_flags: &[Flag], is_exact: bool) -> bool {
+        self.entries(stem).any(|flags| {
+            affix_flags.iter().all(|flag| flags.contains(flag))
+                && !self.has_flag(flags, self.aff.forbidden_word)
+                && self.allows_case(flags, is_exact)
+        })
+    }
+
+    /// Is `word` in the dictionary, either as a root word, or with a prefix,
+    /// a suffix, or both?
+    fn check_form(&self, word: &str, is_exact: bool) -> bool {
+        let is_root = self.entries(word).any(|flags| {
+            !self.has_flag(flags, self.aff.need_affix)
+                && !self.has_flag(flags, self.aff.forbidden_word)
+                && self.allows_case(flags, is_exact)
+        });
+        if is_root {
+            return true;
+        }
+
+        let suffixed = self.strip_suffixes(word, |stem, suffix| {
+            self.has_stem(stem, &[suffix.flag], is_exact)
+        });
+        if suffixed {
+            return true;
+        }
+
+        self.strip_prefixes(word, |stem, prefix| {
+            self.has_stem(stem, &[prefix.flag], is_exact)
+                || (prefix.cross_product
+                    && self.strip_suffixes(stem, |stem, suffix| {
+                        suffix.cross_product
+                            && self.has_stem(stem, &[prefix.flag, suffix.flag], is_exact)
+                    }))
+        })
+    }
+
+    /// Remove each suffix which `word` could end with, and call `f` with the
+    /// stem and suffix, until it returns true.
+    fn strip_suffixes(&self, word: &str, mut f: impl FnMut(&str, &Affix) -> bool) -> bool {
+        word.char_indices()
+            .map(|(i, _)| i)
+            .chain([word.len()])
+            .any(|i| {
+                let (base, add) = word.split_at(i);
+                self.aff.suffixes.by_add.get(add).is_some_and(|suffixes| {
+                    suffixes.iter().any(|suffix| {
+                        let stem = format!("{}{}", base, suffix.strip);
+                        !stem.is_empty() && suffix.condition.matches_end(&stem) && f(&stem, suffix)
+                    })
+                })
+            })
+    }
+
+    /// Remove each prefix which `word` could start with, and call `f` with the
+    /// stem and prefix, until it returns true.
+    fn strip_prefixes(&self, word: &str, mut f: impl FnMut(&str, &Affix) -> bool) -> bool {
+        word.char_indices()
+            .map(|(i, _)| i)
+            .chain([word.len()])
+            .any(|i| {
+                let (add, base) = word.split_at(i);
+                self.aff.prefixes.by_add.get(add).is_some_and(|prefixes| {
+                    prefixes.iter().any(|prefix| {
+                        let stem = format!("{}{}", prefix.strip, base);
+                        !stem.is_empty()
+                            && prefix.condition.matches_start(&stem)
+                            && f(&stem, prefix)
+                    })
+                })
+            })
+    }
+}
+
+/// Split a `.dic` entry into the word and its flags, e.g. `walk/DGS`. A `/` in
+/// the word is escaped as `\/`.
+fn split_entry(entry: &str) -> (String, Option<&str>) {
+    let mut escaped = false;
+    for (i, c) in entry.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '/' if !escaped && i > 0 => {
+                return (entry[..i].replace("\\/", "/"), Some(&entry[i + 1..]));
+            }
+            _ => escaped = false,
+        }
+    }
+    (entry.replace("\\/", "/"), None)
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, DictionaryError> {
+    fs::read(path).map_err(|err| DictionaryError::Io(path.to_path_buf(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{split_entry, Condition, Dictionary, DictionaryError, FlagFormat};
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+KEEPCASE K
+FORBIDDENWORD X
+NOSUGGEST !
+NEEDAFFIX N
+
+REP 2
+REP f ph
+REP alot a_lot
+
+PFX U Y 1
+PFX U 0 un .
+
+SFX S Y 4
+SFX S y ies [^aeiou]y
+SFX S 0 s [aeiou]y
+SFX S 0 es [sxzh]
+SFX S 0 s [^sxzhy]
+
+SFX D Y 4
+SFX D 0 d e
+SFX D y ied [^aeiou]y
+SFX D 0 ed [^ey]
+SFX D 0 ed [aeiou]y
+
+SFX G N 1
+SFX G 0 ing .
+";
+
+    const DIC: &str = "\
+14
+a
+lot
+the
+cat/S
+box/S
+fly/SD
+play/SD
+lock/UDS
+do/U
+Paris
+iPhone/K
+telephone/S
+walk/DGN
+irregardless/X
+";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::new(AFF, DIC).unwrap()
+    }
+
+    #[test]
+    fn test_condition() {
+        let condition = Condition::parse("[^aeiou]y").unwrap();
+        assert!(condition.matches_end("fly"));
+        assert!(!condition.matches_end("play"));
+        assert!(!condition.matches_end("y"));
+        assert!(condition.matches_start("by"));
+
+        assert!(Condition::parse(".").unwrap().matches_end(""));
+        assert!(Condition::parse("[ab").is_err());
+    }
+
+    #[test]
+    fn test_flag_format() {
+        assert_eq!(FlagFormat::Char.parse("AB").unwrap().len(), 2);
+        assert_eq!(FlagFormat::Long.parse("AaBb").unwrap().len(), 2);
+        assert!(FlagFormat::Long.parse("AaB").is_err());
+        assert_eq!(FlagFormat::Num.parse("1,20,300").unwrap(), vec![1, 20, 300]);
+        assert!(FlagFormat::Num.parse("1,x").is_err());
+    }
+
+    #[test]
+    fn test_split_entry() {
+        assert_eq!(split_entry("walk/DGS"), ("walk".into(), Some("DGS")));
+        assert_eq!(split_entry("walk"), ("walk".into(), None));
+        assert_eq!(split_entry("and\\/or/S"), ("and/or".into(), Some("S")));
+        assert_eq!(split_entry("/"), ("/".into(), None));
+    }
+
+    #[test]
+    fn test_check() {
+        let dictionary = dictionary();
+        for word in [
+            "cat", "cats", "boxes", "flies", "flied", "plays", "played", "lock", "unlock",
+            "unlocked", "unlocks", "undo", "walked", "walking",
+        ] {
+            assert!(dictionary.check(word), "{:?} should be correct", word);
+        }
+        for word in [
+            "cates", "flys", "plaies", "unwalk", "undoes", "walk", "lockes", "ct", "",
+        ] {
+            assert!(!dictionary.check(word), "{:?} should be misspelled", word);
+        }
+    }
+
+    #[test]
+    fn test_check_case() {
+        let dictionary = dictionary();
+        assert!(dictionary.check("Cat"));
+        assert!(dictionary.check("CATS"));
+        assert!(!dictionary.check("cAt"));
+
+        assert!(dictionary.check("Paris"));
+        assert!(dictionary.check("PARIS"));
+        assert!(!dictionary.check("paris"));
+
+        // KEEPCASE
+        assert!(dictionary.check("iPhone"));
+        assert!(!dictionary.check("IPHONE"));
+        assert!(!dictionary.check("Iphone"));
+    }
+
+    #[test]
+    fn test_forbidden() {
+        assert!(!dictionary().check("irregardless"));
+        assert!(!dictionary().check("Irregardless"));
+    }
+
+    #[test]
+    fn test_suggest() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.suggest("teh", 5), vec!["the"]);
+        assert_eq!(dictionary.suggest("paris", 5), vec!["Paris"]);
+        assert_eq!(dictionary.suggest("telefone", 5), vec!["telephone"]);
+        assert_eq!(dictionary.suggest("alot", 5), vec!["a lot", "lot"]);
+        assert_eq!(dictionary.suggest("thecat", 5), vec!["the cat"]);
+        assert_eq!(dictionary.suggest("Teh", 5), vec!["The"]);
+        assert_eq!(dictionary.suggest("cst", 5), vec!["cat"]);
+        assert_eq!(dictionary.suggest("catts", 5), vec!["cats"]);
+        assert_eq!(dictionary.suggest("cts", 5), vec!["cats"]);
+        assert_eq!(dictionary.suggest("cat", 5), Vec::<String>::new());
+        assert_eq!(dictionary.suggest("irregardles", 5), Vec::<String>::new());
+        assert_eq!(dictionary.suggest("zzzzzz", 5), Vec::<String>::new());
+        assert!(dictionary.suggest("lcok", 0).is_empty());
+    }
+
+    #[test]
+    fn test_suggest_order() {
+        // "lcok" is "lock" with two letters swapped, which is more likely than
+        // the other edits.
+        let mut dictionary = dictionary();
+        dictionary.add_word("lcoks");
+        assert_eq!(dictionary.suggest("lcok", 5), vec!["lock", "lcoks"]);
+        assert_eq!(dictionary.suggest("lcok", 1), vec!["lock"]);
+    }
+
+    #[test]
+    fn test_add_word() {
+        let mut dictionary = Dictionary::default();
+        assert!(dictionary.is_empty());
+        dictionary.add_word("pastelito");
+        dictionary.add_word("VSCode");
+        assert!(!dictionary.is_empty());
+        assert!(dictionary.check("pastelito"));
+        assert!(dictionary.check("Pastelito"));
+        assert!(dictionary.check("VSCode"));
+        assert!(!dictionary.check("vscode"));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(matches!(
+            Dictionary::new("SFX S Y", DIC),
+            Err(DictionaryError::InvalidAff { line: 1, .. })
+        ));
+        assert!(matches!(
+            Dictionary::new("FLAG num", "1\ncat/x"),
+            Err(DictionaryError::InvalidDic { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("pastelito-dictionary-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // An ISO-8859-1 dictionary.
+        fs::write(dir.join("fr.aff"), b"SET ISO8859-1\n").unwrap();
+        fs::write(dir.join("fr.dic"), b"1\ncaf\xe9\n").unwrap();
+        let dictionary = Dictionary::load(&dir.join("fr.dic")).unwrap();
+        assert!(dictionary.check("café"));
+
+        fs::write(dir.join("words.txt"), "# Project words\n\npastelito\n").unwrap();
+        let dictionary = Dictionary::load_word_list(&dir.join("words.txt")).unwrap();
+        assert!(dictionary.check("pastelito"));
+        assert!(!dictionary.check("#"));
+
+        assert!(matches!(
+            Dictionary::load(&dir.join("missing.dic")),
+            Err(DictionaryError::Io(..))
+        ));
+
+        fs::write(dir.join("ru.aff"), "SET KOI8-R\n").unwrap();
+        fs::write(dir.join("ru.dic"), "0\n").unwrap();
+        assert!(matches!(
+            Dictionary::load(&dir.join("ru.dic")),
+            Err(DictionaryError::UnsupportedEncoding(..))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
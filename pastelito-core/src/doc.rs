@@ -1,11 +1,24 @@
+use std::collections::HashMap;
+
 use tracing::debug_span;
 
-use crate::{block::Block, Tagger, Word};
+use crate::{block::Block, suppress::Suppressions, ByteSpan, Tagger, Word};
 
 /// A parser that converts a string into a sequence of blocks.
 pub trait Parser {
     /// Parse the given data into a list of tokenized blocks.
     fn parse<'input>(&self, input: &'input str) -> Vec<Block<Word<'input>>>;
+
+    /// Parse the given data into a list of tokenized blocks, along with the
+    /// regions of the data where warnings are suppressed.
+    ///
+    /// By default, nothing is suppressed.
+    fn parse_with_suppressions<'input>(
+        &self,
+        input: &'input str,
+    ) -> (Vec<Block<Word<'input>>>, Suppressions) {
+        (self.parse(input), Suppressions::default())
+    }
 }
 
 /// A document, containing a sequence of blocks.
@@ -13,13 +26,15 @@ pub trait Parser {
 pub struct Document<'input> {
     input: &'input str,
     blocks: Vec<Block<Word<'input>>>,
+    suppressions: Suppressions,
 }
 
 impl<'input> Document<'input> {
     /// Create a new document by parsing the input data with the given parser.
     pub fn new(parser: &impl Parser, input: &'input str) -> Self {
         let parse_span = debug_span!("parse");
-        let mut blocks = parse_span.in_scope(|| parser.parse(input));
+        let (mut blocks, suppressions) =
+            parse_span.in_scope(|| parser.parse_with_suppressions(input));
 
         let tagger = Tagger::default();
         let tag_span = debug_span!("tag");
@@ -29,7 +44,100 @@ impl<'input> Document<'input> {
             }
         });
 
-        Document { input, blocks }
+        Document {
+            input,
+            blocks,
+            suppressions,
+        }
+    }
+
+    /// Create a new document from an edited version of this document's input.
+    ///
+    /// `input` must be the result of applying `edit` to the input of this
+    /// document. The whole input is re-parsed, but blocks whose text is not
+    /// affected by the edit keep their existing tags rather than being
+    /// re-tagged. Tagging is the most expensive step, so this is much faster
+    /// than `Document::new` for small edits to large documents.
+    ///
+    /// `parser` must be the same parser that was used to create this document.
+    ///
+    /// The returned `DocumentChanges` describes which blocks were re-tagged and
+    /// can be passed to `RuleSet::apply_incremental`.
+    pub fn update<'new>(
+        &self,
+        parser: &impl Parser,
+        edit: Edit,
+        input: &'new str,
+    ) -> (Document<'new>, DocumentChanges) {
+        assert!(
+            edit.span.end() <= self.input.len()
+                && self.input.len() - edit.span.len() + edit.new_len == input.len(),
+            "edit {:?} does not match the input lengths ({} -> {})",
+            edit,
+            self.input.len(),
+            input.len()
+        );
+
+        let parse_span = debug_span!("parse");
+        let (mut blocks, suppressions) =
+            parse_span.in_scope(|| parser.parse_with_suppressions(input));
+
+        // Index the previous blocks by their span, so we can quickly find the
+        // previous version of each new block.
+        let previous_blocks: HashMap<ByteSpan, &Block<Word<'input>>> = self
+            .blocks
+            .iter()
+            .filter_map(|block| block.as_span().map(|span| (span, block)))
+            .collect();
+
+        let mut changes = DocumentChanges {
+            changed_blocks: Vec::new(),
+            reused_blocks: Vec::new(),
+        };
+
+        let tagger = Tagger::default();
+        let tag_span = debug_span!("tag");
+        tag_span.in_scope(|| {
+            for (i, block) in blocks.iter_mut().enumerate() {
+                let reused = block.as_span().and_then(|span| {
+                    let previous_span = edit.previous_span(span)?;
+                    let previous = previous_blocks.get(&previous_span)?;
+                    copy_tags(previous, block).then_some((previous_span, span))
+                });
+
+                match reused {
+                    Some(spans) => changes.reused_blocks.push(spans),
+                    None => {
+                        tagger.tag(block);
+                        changes.changed_blocks.push(i);
+                    }
+                }
+            }
+        });
+
+        (
+            Document {
+                input,
+                blocks,
+                suppressions,
+            },
+            changes,
+        )
+    }
+
+    /// Create a new document containing a subset of the blocks of this
+    /// document.
+    pub(crate) fn with_blocks(&self, indices: &[usize]) -> Document<'input> {
+        Document {
+            input: self.input,
+            blocks: indices.iter().map(|i| self.blocks[*i].clone()).collect(),
+            suppressions: self.suppressions.clone(),
+        }
+    }
+
+    /// Get the regions of this document where warnings are suppressed.
+    pub fn suppressions(&self) -> &Suppressions {
+        &self.suppressions
     }
 
     /// Get the input data that this document was created from.
@@ -74,3 +182,362 @@ impl<'input> IntoIterator for &'input mut Document<'input> {
         self.blocks.iter_mut()
     }
 }
+
+/// Copy the tags from `previous` to `block`, if both blocks are the same kind
+/// and contain the same words.
+///
+/// Words are compared by their text and their position relative to the start of
+/// the block. Returns `false`, without modifying `block`, if the blocks differ.
+fn copy_tags(previous: &Block<Word>, block: &mut Block<Word>) -> bool {
+    let previous_words = previous.as_slice();
+    let words = block.as_slice();
+
+    if previous.kind() != block.kind()
+        || previous_words.len() != words.len()
+        || previous_words.is_empty()
+    {
+        return false;
+    }
+
+    let previous_start = previous_words[0].as_offset();
+    let start = words[0].as_offset();
+
+    let same_words = previous_words.iter().zip(words).all(|(previous, word)| {
+        previous.as_str() == word.as_str()
+            && previous.as_offset() - previous_start == word.as_offset() - start
+    });
+
+    if !same_words {
+        return false;
+    }
+
+    for (previous, word) in previous_words.iter().zip(block.iter_mut()) {
+        match previous.tag() {
+            Some(tag) => word.set_tag(tag),
+            None => word.clear_tag(),
+        }
+    }
+
+    true
+}
+
+/// An edit to the input data of a document.
+///
+/// The bytes covered by `span` in the previous input were replaced by `new_len`
+/// bytes of new text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Edit {
+    span: ByteSpan,
+    new_len: usize,
+}
+
+impl Edit {
+    /// Create a new edit which replaces `span` of the previous input with
+    /// `new_len` bytes.
+    pub fn new(span: ByteSpan, new_len: usize) -> Self {
+        Edit { span, new_len }
+    }
+
+    /// Find the smallest single edit which transforms `previous` into `input`.
+    ///
+    /// This compares the common prefix and suffix of the two strings, so it
+    /// works for any number of changes. If there are several changes far apart,
+    /// the edit will cover all of them.
+    pub fn between(previous: &str, input: &str) -> Self {
+        let max_len = previous.len().min(input.len());
+
+        let mut prefix = previous
+            .bytes()
+            .zip(input.bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !previous.is_char_boundary(prefix) || !input.is_char_boundary(prefix) {
+            prefix -= 1;
+        }
+
+        let mut suffix = previous
+            .bytes()
+            .rev()
+            .zip(input.bytes().rev())
+            .take(max_len - prefix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !previous.is_char_boundary(previous.len() - suffix)
+            || !input.is_char_boundary(input.len() - suffix)
+        {
+            suffix -= 1;
+        }
+
+        Edit {
+            span: ByteSpan::new_unchecked(prefix, previous.len() - suffix),
+            new_len: input.len() - suffix - prefix,
+        }
+    }
+
+    /// Get the span of the previous input which was replaced.
+    pub fn span(&self) -> ByteSpan {
+        self.span
+    }
+
+    /// Get the length, in bytes, of the replacement text.
+    pub fn new_len(&self) -> usize {
+        self.new_len
+    }
+
+    /// Get the span of a piece of new input in the previous input.
+    ///
+    /// Returns `None` if `span` overlaps the replacement text, as that text did
+    /// not exist in the previous input.
+    fn previous_span(&self, span: ByteSpan) -> Option<ByteSpan> {
+        let new_end = self.span.start() + self.new_len;
+
+        if span.end() <= self.span.start() {
+            Some(span)
+        } else if span.start() >= new_end {
+            Some(span.moved(new_end, self.span.end()))
+        } else {
+            None
+        }
+    }
+}
+
+/// The changes to a document's blocks made by `Document::update`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DocumentChanges {
+    /// The indices of the blocks which were re-tagged.
+    changed_blocks: Vec<usize>,
+    /// The blocks which were reused, as (previous span, new span) pairs. These
+    /// are sorted by span.
+    reused_blocks: Vec<(ByteSpan, ByteSpan)>,
+}
+
+impl DocumentChanges {
+    /// Get the indices of the blocks in the new document which were affected
+    /// by the edit.
+    pub fn changed_blocks(&self) -> &[usize] {
+        &self.changed_blocks
+    }
+
+    /// Find the new span of a span from the previous document.
+    ///
+    /// Returns `None` if `span` is not contained within a reused block.
+    pub(crate) fn moved_span(&self, span: ByteSpan) -> Option<ByteSpan> {
+        let i = self
+            .reused_blocks
+            .partition_point(|(previous, _)| previous.start() <= span.start());
+        let (previous, new) = self.reused_blocks.get(i.checked_sub(1)?)?;
+
+        if previous.contains(&span) {
+            Some(span.moved(previous.start(), new.start()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::{Arbitrary, Gen};
+
+    use crate::{
+        block::{Block, BlockKind},
+        doc::{Document, Edit},
+        parsers::MarkdownParser,
+        rule::RuleSet,
+        ByteSpan, Word,
+    };
+
+    fn blocks<'input>(doc: &Document<'input>) -> Vec<(BlockKind, Vec<Word<'input>>)> {
+        doc.iter()
+            .map(|block: &Block<Word>| (block.kind(), block.as_slice().to_vec()))
+            .collect()
+    }
+
+    /// Apply `edit` to `input`.
+    fn apply(input: &str, edit: Edit, text: &str) -> String {
+        assert_eq!(edit.new_len(), text.len());
+        let mut edited = input.to_string();
+        edited.replace_range(edit.span().start()..edit.span().end(), text);
+        edited
+    }
+
+    /// Check that updating a document and its results gives the same output as
+    /// parsing the edited input from scratch.
+    fn update_eq(input: &str, edited: &str) -> Vec<usize> {
+        let parser = MarkdownParser::default();
+        let ruleset = RuleSet::default();
+
+        let doc = Document::new(&parser, input);
+        let results = ruleset.apply(&doc);
+
+        let edit = Edit::between(input, edited);
+        let (updated, changes) = doc.update(&parser, edit, edited);
+        let updated_results = ruleset.apply_incremental(&updated, &changes, &results);
+
+        let expected = Document::new(&parser, edited);
+        let expected_results = ruleset.apply(&expected);
+
+        assert_eq!(blocks(&updated), blocks(&expected), "edited={:?}", edited);
+        assert_eq!(
+            updated_results.iter_warnings().collect::<Vec<_>>(),
+            expected_results.iter_warnings().collect::<Vec<_>>(),
+            "edited={:?}",
+            edited
+        );
+        assert_eq!(
+            updated_results.iter_measurements().collect::<Vec<_>>(),
+            expected_results.iter_measurements().collect::<Vec<_>>(),
+            "edited={:?}",
+            edited
+        );
+        assert_eq!(
+            updated_results.readability(),
+            expected_results.readability(),
+            "edited={:?}",
+            edited
+        );
+
+        changes.changed_blocks().to_vec()
+    }
+
+    #[test]
+    fn test_edit_between() {
+        fn eq(previous: &str, input: &str, start: usize, end: usize, new_len: usize) {
+            let edit = Edit::between(previous, input);
+            assert_eq!(
+                edit,
+                Edit::new(ByteSpan::new_unchecked(start, end), new_len)
+            );
+            let text = &input[start..start + new_len];
+            assert_eq!(apply(previous, edit, text), input);
+        }
+
+        eq("", "", 0, 0, 0);
+        eq("abc", "abc", 3, 3, 0);
+        eq("abc", "abXc", 2, 2, 1);
+        eq("abc", "ac", 1, 2, 0);
+        eq("abc", "aXc", 1, 2, 1);
+        eq("abc", "", 0, 3, 0);
+        eq("", "abc", 0, 0, 3);
+        eq("aaa", "aaaa", 3, 3, 1);
+        // Multibyte characters which share a leading byte.
+        eq("a🦕b", "a🦖b", 1, 5, 4);
+        eq("🦕", "🦕🦕", 4, 4, 4);
+    }
+
+    #[test]
+    fn test_update() {
+        let input = "It was quite big.\n\nThe cat sat.\n\nMistakes were made.";
+
+        // Edit the middle block.
+        assert_eq!(
+            update_eq(
+                input,
+                "It was quite big.\n\nThe big dog sat.\n\nMistakes were made."
+            ),
+            vec![1]
+        );
+
+        // Insert a new block at the start, which moves all the later blocks.
+        assert_eq!(update_eq(input, &format!("# Title\n\n{}", input)), vec![0]);
+
+        // Merge two blocks.
+        assert_eq!(
+            update_eq(
+                input,
+                "It was quite big.\nThe cat sat.\n\nMistakes were made."
+            ),
+            vec![0]
+        );
+
+        // Turn a paragraph into a heading.
+        assert_eq!(
+            update_eq(
+                input,
+                "It was quite big.\n\n# The cat sat.\n\nMistakes were made."
+            ),
+            vec![1]
+        );
+
+        // Delete everything.
+        assert_eq!(update_eq(input, ""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_update_unchanged() {
+        let input = "It was quite big.\n\nThe cat sat.";
+        assert_eq!(update_eq(input, input), Vec::<usize>::new());
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_update_invalid_edit() {
+        let parser = MarkdownParser::default();
+        let doc = Document::new(&parser, "abc");
+        let _ = doc.update(&parser, Edit::new(ByteSpan::new_unchecked(0, 1), 1), "");
+    }
+
+    /// Pieces of Markdown used to build arbitrary documents and edits.
+    const PIECES: &[&str] = &[
+        "The",
+        "the",
+        " ",
+        " ",
+        "\n",
+        "\n\n",
+        "# ",
+        "* ",
+        "was",
+        "quite",
+        "big",
+        "cat",
+        ".",
+        ",",
+        "made",
+        "were",
+        "🦕",
+        "`code`",
+        "*",
+        "on",
+        "<!-- pastelito-disable-next-line -->",
+        "<!-- pastelito-disable -->",
+        "<!-- pastelito-enable -->",
+    ];
+
+    #[derive(Clone, Debug)]
+    struct ArbitraryEdit {
+        input: String,
+        edited: String,
+    }
+
+    impl Arbitrary for ArbitraryEdit {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let pieces = |g: &mut Gen| {
+                let len = usize::arbitrary(g) % 40;
+                (0..len)
+                    .map(|_| *g.choose(PIECES).unwrap())
+                    .collect::<String>()
+            };
+
+            let input = pieces(g);
+            let boundaries = input
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([input.len()])
+                .collect::<Vec<_>>();
+            let a = *g.choose(&boundaries).unwrap();
+            let b = *g.choose(&boundaries).unwrap();
+
+            let mut edited = input.clone();
+            edited.replace_range(a.min(b)..a.max(b), &pieces(g));
+
+            ArbitraryEdit { input, edited }
+        }
+    }
+
+    #[quickcheck]
+    fn update_matches_new(edit: ArbitraryEdit) -> bool {
+        update_eq(&edit.input, &edit.edited);
+        true
+    }
+}
@@ -0,0 +1,253 @@
+//! Project glossaries, loaded from TOML files.
+//!
+//! A glossary lists the preferred spelling of each term, and any variants
+//! which should be replaced by it. A term can be restricted to a part of
+//! speech, so a phrase can be spelled differently as a noun and as a verb.
+//! For example:
+//!
+//! ```toml
+//! [[terms]]
+//! term = "GitHub"
+//!
+//! [[terms]]
+//! term = "email"
+//! variants = ["e-mail"]
+//!
+//! [[terms]]
+//! term = "sign in"
+//! pos = "verb"
+//! variants = ["sign-in", "signin"]
+//!
+//! [[terms]]
+//! term = "sign-in"
+//! pos = "noun"
+//! variants = ["sign in", "signin"]
+//! ```
+//!
+//! The `pos` is one of `noun`, `verb`, `adjective` or `adverb`, and is
+//! checked using the tags of the matched words. Terms are also checked for
+//! their capitalization, so "Github" is flagged as well as any variants. A
+//! term starting with a lowercase letter may also be capitalized, e.g. at the
+//! start of a sentence.
+use std::{fmt, fs, io, path::Path};
+
+use pastelito_model::Tag;
+use serde::Deserialize;
+
+/// An error when loading a glossary.
+#[derive(Debug)]
+pub enum GlossaryError {
+    /// The glossary could not be read.
+    Io(io::Error),
+    /// The glossary is not valid TOML, or does not match the expected schema.
+    Toml(toml::de::Error),
+    /// A term in the glossary is invalid.
+    InvalidTerm {
+        /// The index of the term in the glossary.
+        index: usize,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for GlossaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlossaryError::Io(err) => write!(f, "{}", err),
+            GlossaryError::Toml(err) => write!(f, "{}", err),
+            GlossaryError::InvalidTerm { index, message } => {
+                write!(f, "term {}: {}", index, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GlossaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GlossaryError::Io(err) => Some(err),
+            GlossaryError::Toml(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A part of speech, which a glossary term can be restricted to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+}
+
+impl PartOfSpeech {
+    /// Is a word with `tag` this part of speech?
+    pub fn matches(self, tag: Tag) -> bool {
+        match self {
+            PartOfSpeech::Noun => matches!(
+                tag,
+                Tag::NounSingularOrMass
+                    | Tag::NounPlural
+                    | Tag::ProperNounSingular
+                    | Tag::ProperNounPlural
+            ),
+            PartOfSpeech::Verb => matches!(
+                tag,
+                Tag::VerbBaseForm
+                    | Tag::VerbPastTense
+                    | Tag::VerbGerundOrPresentParticiple
+                    | Tag::VerbPastParticiple
+                    | Tag::VerbNon3rdPersonSingularPresent
+                    | Tag::Verb3rdPersonSingularPresent
+            ),
+            PartOfSpeech::Adjective => matches!(
+                tag,
+                Tag::Adjective | Tag::AdjectiveComparative | Tag::AdjectiveSuperlative
+            ),
+            PartOfSpeech::Adverb => matches!(
+                tag,
+                Tag::Adverb | Tag::AdverbComparative | Tag::AdverbSuperlative
+            ),
+        }
+    }
+
+    /// The name of the part of speech, e.g. `"noun"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartOfSpeech::Noun => "noun",
+            PartOfSpeech::Verb => "verb",
+            PartOfSpeech::Adjective => "adjective",
+            PartOfSpeech::Adverb => "adverb",
+        }
+    }
+}
+
+/// A term in a glossary.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Term {
+    /// The preferred spelling of the term, e.g. "GitHub".
+    pub term: String,
+    /// Spellings which should be replaced by the term, e.g. "e-mail" for
+    /// "email".
+    #[serde(default)]
+    pub variants: Vec<String>,
+    /// If set, the term and its variants are only checked when used as this
+    /// part of speech.
+    #[serde(default)]
+    pub pos: Option<PartOfSpeech>,
+}
+
+impl Term {
+    /// Check that the term and its variants are not empty.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.term.trim().is_empty() {
+            return Err("empty term".into());
+        }
+        if self
+            .variants
+            .iter()
+            .any(|variant| variant.trim().is_empty())
+        {
+            return Err(format!("empty variant for {:?}", self.term));
+        }
+        Ok(())
+    }
+}
+
+/// A glossary of terms.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Glossary {
+    pub terms: Vec<Term>,
+}
+
+impl Glossary {
+    /// Parse a glossary from TOML.
+    pub fn from_toml_str(input: &str) -> Result<Self, GlossaryError> {
+        let glossary: Glossary = toml::from_str(input).map_err(GlossaryError::Toml)?;
+        for (index, term) in glossary.terms.iter().enumerate() {
+            term.validate()
+                .map_err(|message| GlossaryError::InvalidTerm { index, message })?;
+        }
+        Ok(glossary)
+    }
+
+    /// Load a glossary file.
+    pub fn load(path: &Path) -> Result<Self, GlossaryError> {
+        let input = fs::read_to_string(path).map_err(GlossaryError::Io)?;
+        Self::from_toml_str(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pastelito_model::Tag;
+
+    use super::{Glossary, GlossaryError, PartOfSpeech, Term};
+
+    #[test]
+    fn test_parse() {
+        let glossary = Glossary::from_toml_str(
+            r#"
+            [[terms]]
+            term = "GitHub"
+
+            [[terms]]
+            term = "sign-in"
+            pos = "noun"
+            variants = ["sign in"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            glossary.terms,
+            vec![
+                Term {
+                    term: "GitHub".into(),
+                    variants: vec![],
+                    pos: None,
+                },
+                Term {
+                    term: "sign-in".into(),
+                    variants: vec!["sign in".into()],
+                    pos: Some(PartOfSpeech::Noun),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Glossary::from_toml_str("[[terms]]\nterm = \"email\"\npos = \"pronoun\""),
+            Err(GlossaryError::Toml(_))
+        ));
+        assert!(matches!(
+            Glossary::from_toml_str("[[terms]]\nterm = \"email\"\nspelling = \"e-mail\""),
+            Err(GlossaryError::Toml(_))
+        ));
+        assert!(matches!(
+            Glossary::from_toml_str("[[terms]]\nterm = \"email\"\n\n[[terms]]\nterm = \" \""),
+            Err(GlossaryError::InvalidTerm { index: 1, .. })
+        ));
+        assert_eq!(
+            Glossary::from_toml_str("[[terms]]\nterm = \"email\"\nvariants = [\"\"]")
+                .unwrap_err()
+                .to_string(),
+            "term 0: empty variant for \"email\""
+        );
+    }
+
+    #[test]
+    fn test_part_of_speech() {
+        assert!(PartOfSpeech::Noun.matches(Tag::NounPlural));
+        assert!(!PartOfSpeech::Noun.matches(Tag::VerbBaseForm));
+        assert!(PartOfSpeech::Verb.matches(Tag::VerbBaseForm));
+        assert!(PartOfSpeech::Adjective.matches(Tag::AdjectiveSuperlative));
+        assert!(PartOfSpeech::Adverb.matches(Tag::Adverb));
+    }
+}
@@ -7,15 +7,24 @@
 extern crate quickcheck_macros;
 
 mod block;
+pub mod config;
+pub mod dictionary;
 pub mod doc;
+pub mod glossary;
 pub mod lines;
 mod matcher;
 mod measures;
 pub mod parsers;
 mod perceptron;
+mod phrases;
+pub mod readability;
 pub mod rule;
+pub mod rule_file;
 mod rules;
+mod sentence;
+pub mod snapshot;
 mod span;
+pub mod suppress;
 mod tagger;
 mod tokenize;
 
@@ -23,5 +32,7 @@ pub use block::Block;
 pub use block::Word;
 pub use doc::Document;
 pub use lines::LineCharRange;
+pub use sentence::Sentences;
+pub use snapshot::Snapshot;
 pub use span::ByteSpan;
 pub use tagger::Tagger;
@@ -82,7 +82,7 @@ impl EndsWithIgnoreCase {
     /// Create a new pattern.
     ///
     /// This will panic if `suffix` contains any non-ASCII characters.
-    pub fn new(suffix: &'static str) -> Self {
+    pub fn new(suffix: &str) -> Self {
         let suffix_reversed: SmallVec<[char; 8]> = suffix.chars().rev().collect();
 
         if suffix_reversed.iter().any(|c| !c.is_ascii()) {
@@ -305,34 +305,6 @@ impl<P: SingleWordPattern, const N: usize> SingleWordPattern for OneOfS<P, N> {
     }
 }
 
-/// A pattern that matches if any of a set of multiple word patterns match a sequence of words.
-#[derive(Copy, Clone, Debug)]
-pub struct OneOf<P, const N: usize>(pub [P; N]);
-
-impl<P: MultipleWordPattern, const N: usize> MultipleWordPattern for OneOf<P, N> {
-    fn size_hint(&self) -> usize {
-        self.0
-            .iter()
-            .map(|pattern| pattern.size_hint())
-            .max()
-            .unwrap_or(1)
-    }
-
-    fn matches<'input>(
-        &self,
-        matched_words: &mut Vec<Word<'input>>,
-        words: &[Word<'input>],
-    ) -> Option<usize> {
-        for pattern in self.0.iter() {
-            if let Some(next) = pattern.matches(matched_words, words) {
-                return Some(next);
-            }
-        }
-
-        None
-    }
-}
-
 /// A pattern that matches an optional multiple word pattern.
 #[derive(Copy, Clone, Debug)]
 pub struct Opt<P>(pub P);
@@ -374,6 +346,14 @@ pub trait Matcher {
 
     /// Get the pattern to search for.
     fn pattern(&self) -> &Self::Pattern;
+
+    /// Should matches be restricted to a single sentence?
+    ///
+    /// By default, this returns `false`, so matches can cross sentence
+    /// boundaries within a block.
+    fn within_sentence(&self) -> bool {
+        false
+    }
 }
 
 /// All multiple word patterns are also matchers, which do not ignore any words.
@@ -403,8 +383,31 @@ impl<I: SingleWordPattern, P: MultipleWordPattern> Matcher for Ignore<I, P> {
     }
 }
 
+/// A matcher that never matches across a sentence boundary.
+#[derive(Copy, Clone)]
+pub struct WithinSentence<M>(pub M);
+
+impl<M: Matcher> Matcher for WithinSentence<M> {
+    type IgnorePattern = M::IgnorePattern;
+    fn ignore_pattern(&self) -> Option<&Self::IgnorePattern> {
+        self.0.ignore_pattern()
+    }
+
+    type Pattern = M::Pattern;
+    fn pattern(&self) -> &Self::Pattern {
+        self.0.pattern()
+    }
+
+    fn within_sentence(&self) -> bool {
+        true
+    }
+}
+
 /// Find each sequence of words in `block` that match `pattern`, and call
 /// `on_match` with the matched words.
+///
+/// If `matcher.within_sentence()` is true, each sentence in the block is
+/// matched separately.
 pub fn match_words<'input, 'm, M>(
     block: &Block<Word<'input>>,
     matcher: &'m M,
@@ -412,8 +415,24 @@ pub fn match_words<'input, 'm, M>(
 ) where
     M: Matcher,
 {
-    let original_words = block.as_slice();
+    if matcher.within_sentence() {
+        for sentence in block.sentences() {
+            match_slice(sentence, matcher, &mut on_match);
+        }
+    } else {
+        match_slice(block.as_slice(), matcher, &mut on_match);
+    }
+}
 
+/// Find each sequence of words in `original_words` that match `pattern`, and
+/// call `on_match` with the matched words.
+fn match_slice<'input, 'm, M>(
+    original_words: &[Word<'input>],
+    matcher: &'m M,
+    mut on_match: impl FnMut(&[Word<'input>]),
+) where
+    M: Matcher,
+{
     let ignore_pattern = matcher.ignore_pattern();
 
     // If an ignore pattern is provided, filter out any words that match it.
@@ -465,7 +484,9 @@ mod tests {
         matcher::match_words,
     };
 
-    use super::{Any, EndsWithIgnoreCase, Ignore, IgnoreCase, Matcher, Opt, Or, TagFn};
+    use super::{
+        Any, EndsWithIgnoreCase, Ignore, IgnoreCase, Matcher, Opt, Or, TagFn, WithinSentence,
+    };
 
     fn eq<P: Matcher>(pattern: P, expected: Vec<Vec<&str>>) {
         let words = &[
@@ -579,6 +600,52 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_within_sentence() {
+        let words = &[
+            TestWord::Word("The", Tag::Determiner),
+            TestWord::Space,
+            TestWord::Word("cat", Tag::NounSingularOrMass),
+            TestWord::Space,
+            TestWord::Word("sat", Tag::VerbPastTense),
+            TestWord::Word(".", Tag::EndOfSentence),
+            TestWord::Space,
+            TestWord::Word("The", Tag::Determiner),
+            TestWord::Space,
+            TestWord::Word("mat", Tag::NounSingularOrMass),
+            TestWord::Word(".", Tag::EndOfSentence),
+        ];
+
+        fn eq_words<M: Matcher>(words: &[TestWord], matcher: M, expected: Vec<Vec<&str>>) {
+            with_testing_block(words, |_, block| {
+                let mut matches: Vec<Vec<&str>> = Vec::new();
+                match_words(&block, &matcher, |words| {
+                    matches.push(words.iter().map(|word| word.as_str()).collect());
+                });
+                assert_eq!(matches, expected);
+            });
+        }
+
+        let pattern = (Tag::VerbPastTense, Tag::EndOfSentence, Tag::Determiner);
+        eq_words(words, pattern, vec![vec!["sat", ".", "The"]]);
+        eq_words(words, WithinSentence(pattern), vec![]);
+
+        // Patterns within a sentence still match.
+        let pattern = (Tag::Determiner, Tag::NounSingularOrMass);
+        eq_words(
+            words,
+            WithinSentence(pattern),
+            vec![vec!["The", "cat"], vec!["The", "mat"]],
+        );
+
+        // Ignored words are still ignored.
+        eq_words(
+            words,
+            WithinSentence(Ignore(Tag::Determiner, pattern.1)),
+            vec![vec!["cat"], vec!["mat"]],
+        );
+    }
+
     #[test]
     fn test_backtracking() {
         eq(
@@ -4,6 +4,7 @@ use crate::{
     block::{Block, BlockKind},
     doc::Parser,
     span::{ByteSpan, FullByteSpan},
+    suppress::{Suppressions, SuppressionsBuilder},
     tokenize::Tokenizer,
     Word,
 };
@@ -23,9 +24,9 @@ impl BlockBuilder {
         }
     }
 
-    fn new_heading() -> Self {
+    fn new_heading(level: u8) -> Self {
         BlockBuilder {
-            kind: BlockKind::Heading,
+            kind: BlockKind::Heading(level),
             spans: Vec::new(),
         }
     }
@@ -44,6 +45,7 @@ struct ParseState<'input, 't> {
     blocks: Vec<Block<Word<'input>>>,
     stack: Vec<BlockBuilder>,
     tokenizer: &'t Tokenizer,
+    suppressions: SuppressionsBuilder<'input>,
 }
 
 impl<'input, 't> ParseState<'input, 't> {
@@ -53,6 +55,7 @@ impl<'input, 't> ParseState<'input, 't> {
             blocks: Vec::new(),
             stack: Vec::new(),
             tokenizer,
+            suppressions: SuppressionsBuilder::new(input),
         }
     }
 
@@ -64,8 +67,8 @@ impl<'input, 't> ParseState<'input, 't> {
         self.pop_current_block();
     }
 
-    fn start_heading(&mut self) {
-        self.stack.push(BlockBuilder::new_heading());
+    fn start_heading(&mut self, level: u8) {
+        self.stack.push(BlockBuilder::new_heading(level));
     }
 
     fn end_heading(&mut self) {
@@ -85,9 +88,13 @@ impl<'input, 't> ParseState<'input, 't> {
         block.spans.push(span);
     }
 
-    fn finish(self) -> Vec<Block<Word<'input>>> {
+    fn push_html(&mut self, span: ByteSpan) {
+        self.suppressions.comments(span);
+    }
+
+    fn finish(self) -> (Vec<Block<Word<'input>>>, Suppressions) {
         if self.stack.is_empty() {
-            self.blocks
+            (self.blocks, self.suppressions.build())
         } else {
             panic!("unbalanced blocks: {:?}", self.stack);
         }
@@ -114,6 +121,13 @@ impl Default for MarkdownParser {
 
 impl Parser for MarkdownParser {
     fn parse<'input>(&self, input: &'input str) -> Vec<Block<Word<'input>>> {
+        self.parse_with_suppressions(input).0
+    }
+
+    fn parse_with_suppressions<'input>(
+        &self,
+        input: &'input str,
+    ) -> (Vec<Block<Word<'input>>>, Suppressions) {
         let parser = CmarkParser::new_ext(
             input,
             Options::ENABLE_TABLES
@@ -136,9 +150,12 @@ impl Parser for MarkdownParser {
         for (event, range) in parser.into_offset_iter() {
             match event {
                 Event::Start(tag) => match tag {
+                    Tag::HtmlBlock => {
+                        state.push_html(ByteSpan::of_range(range));
+                        state.start_paragraph();
+                    }
                     Tag::Paragraph
                     | Tag::Item
-                    | Tag::HtmlBlock
                     | Tag::TableCell
                     | Tag::DefinitionListTitle
                     | Tag::DefinitionListDefinition
@@ -146,8 +163,8 @@ impl Parser for MarkdownParser {
                     | Tag::FootnoteDefinition(_) => {
                         state.start_paragraph();
                     }
-                    Tag::Heading { .. } => {
-                        state.start_heading();
+                    Tag::Heading { level, .. } => {
+                        state.start_heading(level as u8);
                     }
                     Tag::CodeBlock(_) | Tag::MetadataBlock(_) => {
                         ignore_text = true;
@@ -194,6 +211,9 @@ impl Parser for MarkdownParser {
                         }
                     }
                 }
+                Event::InlineHtml(_) => {
+                    state.push_html(ByteSpan::of_range(range));
+                }
                 _ => {}
             }
         }
@@ -228,8 +248,8 @@ mod tests {
         (BlockKind::Paragraph, strs.to_vec())
     }
 
-    fn h(strs: Vec<&str>) -> (BlockKind, Vec<&str>) {
-        (BlockKind::Heading, strs.to_vec())
+    fn h(level: u8, strs: Vec<&str>) -> (BlockKind, Vec<&str>) {
+        (BlockKind::Heading(level), strs.to_vec())
     }
 
     #[test]
@@ -304,14 +324,19 @@ mod tests {
 
     #[test]
     fn test_heading() {
-        eq("# aaa", vec![h(vec!["aaa"])]);
-        eq("# aaa\n## bbb", vec![h(vec!["aaa"]), h(vec!["bbb"])]);
-        eq("# aaa\n\nbbb", vec![h(vec!["aaa"]), p(vec!["bbb"])]);
+        eq("# aaa", vec![h(1, vec!["aaa"])]);
+        eq("# aaa\n## bbb", vec![h(1, vec!["aaa"]), h(2, vec!["bbb"])]);
+        eq("# aaa\n\nbbb", vec![h(1, vec!["aaa"]), p(vec!["bbb"])]);
         eq(
             "# aaa\nbbb\n\nccc",
-            vec![h(vec!["aaa"]), p(vec!["bbb"]), p(vec!["ccc"])],
+            vec![h(1, vec!["aaa"]), p(vec!["bbb"]), p(vec!["ccc"])],
+        );
+        eq("aaa\n# bbb", vec![p(vec!["aaa"]), h(1, vec!["bbb"])]);
+        eq("###### aaa", vec![h(6, vec!["aaa"])]);
+        eq(
+            "aaa\n===\n\nbbb\n---",
+            vec![h(1, vec!["aaa"]), h(2, vec!["bbb"])],
         );
-        eq("aaa\n# bbb", vec![p(vec!["aaa"]), h(vec!["bbb"])]);
     }
 
     #[test]
@@ -391,7 +416,7 @@ mod tests {
 
     #[test]
     fn test_heading_attributes() {
-        eq("# aaa {#id .class key=value}", vec![h(vec!["aaa"])]);
+        eq("# aaa {#id .class key=value}", vec![h(1, vec!["aaa"])]);
     }
 
     #[test]
@@ -0,0 +1,178 @@
+//! Find phrases from a dictionary in a block of words.
+use std::{cmp::Reverse, collections::HashMap};
+
+use crate::{block::BlockKind, tokenize::Tokenizer, Block, ByteSpan, Word};
+
+/// A dictionary of phrases, each with a value of type `T`.
+///
+/// Phrases are split into words in the same way as documents, so phrases with
+/// contractions or punctuation match the words of a block. Words are compared
+/// ignoring ASCII case.
+#[derive(Clone, Debug)]
+pub(crate) struct Phrases<T> {
+    /// The phrases, indexed by their lowercase first word. Each list is sorted
+    /// longest phrase first, so that the longest phrase is matched.
+    by_first_word: HashMap<String, Vec<Phrase<T>>>,
+}
+
+#[derive(Clone, Debug)]
+struct Phrase<T> {
+    /// The lowercase words of the phrase.
+    words: Vec<String>,
+    value: T,
+}
+
+impl<T> Default for Phrases<T> {
+    fn default() -> Self {
+        Phrases {
+            by_first_word: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Phrases<T> {
+    /// Add a phrase to the dictionary.
+    ///
+    /// If the dictionary already contains the phrase, its value is replaced.
+    /// Empty phrases are ignored.
+    pub(crate) fn insert(&mut self, phrase: &str, value: T) {
+        let block = Block::singleton(
+            BlockKind::Paragraph,
+            ByteSpan::new_unchecked(0, phrase.len()),
+        );
+        let words = Tokenizer::default()
+            .tokenize(phrase, block)
+            .iter()
+            .map(|word| word.as_str().to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        let Some(first) = words.first() else {
+            return;
+        };
+
+        let phrases = self.by_first_word.entry(first.clone()).or_default();
+        match phrases.iter_mut().find(|phrase| phrase.words == words) {
+            Some(existing) => existing.value = value,
+            None => {
+                phrases.push(Phrase { words, value });
+                phrases.sort_by_key(|phrase| Reverse(phrase.words.len()));
+            }
+        }
+    }
+
+    /// Find the longest phrase at the start of `words`.
+    fn longest_prefix(&self, words: &[Word]) -> Option<&Phrase<T>> {
+        let first = words.first()?.as_str().to_ascii_lowercase();
+        self.by_first_word.get(&first)?.iter().find(|phrase| {
+            phrase.words.len() <= words.len()
+                && phrase
+                    .words
+                    .iter()
+                    .zip(words)
+                    .all(|(expected, word)| expected.eq_ignore_ascii_case(word.as_str()))
+        })
+    }
+
+    /// Find every phrase in `words`, from left to right.
+    ///
+    /// Matches don't overlap. If several phrases start at the same word, the
+    /// longest phrase is matched.
+    pub(crate) fn find_all<'a, 'input>(
+        &'a self,
+        words: &'a [Word<'input>],
+    ) -> impl Iterator<Item = (&'a [Word<'input>], &'a T)> + 'a {
+        let mut i = 0;
+        std::iter::from_fn(move || {
+            while i < words.len() {
+                if let Some(phrase) = self.longest_prefix(&words[i..]) {
+                    let matched = &words[i..i + phrase.words.len()];
+                    i += phrase.words.len();
+                    return Some((matched, &phrase.value));
+                }
+                i += 1;
+            }
+            None
+        })
+    }
+}
+
+/// Change the case of `replacement` to match the text it replaces.
+///
+/// If `matched` starts with an uppercase letter, so does the result, e.g. "In
+/// order to" is replaced by "To" rather than "to".
+pub(crate) fn match_case(matched: &str, replacement: &str) -> String {
+    let is_capitalized = matched.chars().next().is_some_and(char::is_uppercase);
+    let mut chars = replacement.chars();
+    match chars.next() {
+        Some(first) if is_capitalized => first.to_uppercase().chain(chars).collect(),
+        _ => replacement.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{doc::Document, parsers::PlaintextParser, ByteSpan};
+
+    use super::{match_case, Phrases};
+
+    fn find_eq(phrases: &Phrases<u32>, input: &str, expected: Vec<(&str, u32)>) {
+        let doc = Document::new(&PlaintextParser::default(), input);
+        let found = doc
+            .iter()
+            .flat_map(|block| {
+                phrases
+                    .find_all(block.as_slice())
+                    .map(|(words, value)| {
+                        let span: ByteSpan = words.into();
+                        (span.as_str(input), *value)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected, "input={:?}", input);
+    }
+
+    fn phrases() -> Phrases<u32> {
+        let mut phrases = Phrases::default();
+        phrases.insert("in order", 1);
+        phrases.insert("in order to", 2);
+        phrases.insert("order", 3);
+        phrases.insert("doesn't", 4);
+        phrases.insert("", 5);
+        phrases
+    }
+
+    #[test]
+    fn test_find_all() {
+        let phrases = phrases();
+        find_eq(&phrases, "", vec![]);
+        find_eq(&phrases, "It is fine.", vec![]);
+        find_eq(
+            &phrases,
+            "We left in order to eat.",
+            vec![("in order to", 2)],
+        );
+        find_eq(&phrases, "It is in order.", vec![("in order", 1)]);
+        find_eq(&phrases, "The order is in.", vec![("order", 3)]);
+        find_eq(
+            &phrases,
+            "In Order To eat, we order food.",
+            vec![("In Order To", 2), ("order", 3)],
+        );
+        find_eq(&phrases, "It doesn't work.", vec![("doesn't", 4)]);
+    }
+
+    #[test]
+    fn test_insert_replaces() {
+        let mut phrases = phrases();
+        phrases.insert("In Order", 6);
+        find_eq(&phrases, "It is in order.", vec![("in order", 6)]);
+    }
+
+    #[test]
+    fn test_match_case() {
+        assert_eq!(match_case("in order to", "to"), "to");
+        assert_eq!(match_case("In order to", "to"), "To");
+        assert_eq!(match_case("In order to", ""), "");
+        assert_eq!(match_case("", "to"), "to");
+    }
+}
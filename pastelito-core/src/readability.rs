@@ -0,0 +1,389 @@
+//! Readability scores for documents and blocks.
+//!
+//! The scores are computed from counts of sentences, words, syllables and
+//! letters. The counts for each block are stored in `Results`, so the scores
+//! for the whole document can be updated incrementally.
+use std::ops::{Add, AddAssign};
+
+use crate::{rule::HasSpan, Block, ByteSpan, Document, Word};
+
+/// Counts of the features of some text used by the readability formulas.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextCounts {
+    /// The number of sentences.
+    pub sentences: usize,
+    /// The number of words, excluding punctuation.
+    pub words: usize,
+    /// The number of syllables in all the words.
+    pub syllables: usize,
+    /// The number of words with three or more syllables.
+    pub polysyllables: usize,
+    /// The number of words with three or more syllables, excluding proper
+    /// nouns. These are the "complex words" used by the Gunning fog index.
+    pub complex_words: usize,
+    /// The number of letters and digits in all the words.
+    pub letters: usize,
+}
+
+impl TextCounts {
+    /// Count the features of a single block.
+    ///
+    /// Sentences are found with `Block::sentences`.
+    pub fn of_block(block: &Block<Word>) -> Self {
+        block
+            .sentences()
+            .fold(TextCounts::default(), |counts, sentence| {
+                counts + TextCounts::of_sentence(sentence)
+            })
+    }
+
+    /// Count the features of a single sentence.
+    ///
+    /// A sentence without any words, e.g. only punctuation, is not counted.
+    pub fn of_sentence(sentence: &[Word]) -> Self {
+        let mut counts = TextCounts::default();
+
+        for word in sentence {
+            let str = word.as_str();
+            if !is_word(str) {
+                continue;
+            }
+
+            let syllables = count_syllables(str);
+            counts.words += 1;
+            counts.syllables += syllables;
+            counts.letters += str.chars().filter(|c| c.is_alphanumeric()).count();
+            if syllables >= 3 {
+                counts.polysyllables += 1;
+                if !word.is_proper_noun() {
+                    counts.complex_words += 1;
+                }
+            }
+        }
+
+        if counts.words > 0 {
+            counts.sentences = 1;
+        }
+
+        counts
+    }
+
+    /// Get the readability scores for these counts.
+    ///
+    /// Returns `None` if there are no words.
+    pub fn scores(&self) -> Option<Readability> {
+        if self.words == 0 || self.sentences == 0 {
+            return None;
+        }
+
+        let words = self.words as f64;
+        let sentences = self.sentences as f64;
+        let words_per_sentence = words / sentences;
+        let syllables_per_word = self.syllables as f64 / words;
+
+        // Coleman-Liau uses the average number of letters and sentences per
+        // 100 words.
+        let letters_per_100 = self.letters as f64 / words * 100.0;
+        let sentences_per_100 = sentences / words * 100.0;
+
+        Some(Readability {
+            flesch_reading_ease: 206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word,
+            flesch_kincaid_grade: 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59,
+            gunning_fog: 0.4 * (words_per_sentence + 100.0 * self.complex_words as f64 / words),
+            smog: 1.043 * (self.polysyllables as f64 * 30.0 / sentences).sqrt() + 3.1291,
+            coleman_liau: 0.0588 * letters_per_100 - 0.296 * sentences_per_100 - 15.8,
+        })
+    }
+}
+
+impl Add for TextCounts {
+    type Output = TextCounts;
+
+    fn add(mut self, other: TextCounts) -> TextCounts {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for TextCounts {
+    fn add_assign(&mut self, other: TextCounts) {
+        self.sentences += other.sentences;
+        self.words += other.words;
+        self.syllables += other.syllables;
+        self.polysyllables += other.polysyllables;
+        self.complex_words += other.complex_words;
+        self.letters += other.letters;
+    }
+}
+
+/// Readability scores for some text.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Readability {
+    /// The Flesch reading ease. Higher scores are easier to read; most
+    /// documents score between 0 and 100.
+    pub flesch_reading_ease: f64,
+    /// The Flesch-Kincaid grade level, as a US school grade.
+    pub flesch_kincaid_grade: f64,
+    /// The Gunning fog index, as the years of formal education needed.
+    pub gunning_fog: f64,
+    /// The SMOG grade, as the years of education needed.
+    pub smog: f64,
+    /// The Coleman-Liau index, as a US school grade.
+    pub coleman_liau: f64,
+}
+
+/// A readability grade, e.g. a Flesch-Kincaid grade level.
+///
+/// Grades are compared with `f64::total_cmp`, so that they can be stored in
+/// types which are sorted, such as `Warning`.
+#[derive(Copy, Clone, Debug)]
+pub struct Grade(f64);
+
+impl Grade {
+    /// Create a new grade.
+    pub fn new(grade: f64) -> Self {
+        Grade(grade)
+    }
+
+    /// Get the value of the grade.
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl PartialEq for Grade {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Grade {}
+
+impl PartialOrd for Grade {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Grade {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// The counts for a single block.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BlockReadability {
+    /// The span of the block.
+    pub span: ByteSpan,
+    /// The counts for the block.
+    pub counts: TextCounts,
+}
+
+impl HasSpan for &BlockReadability {
+    fn span(&self) -> ByteSpan {
+        self.span
+    }
+}
+
+/// The counts for each block in a document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DocumentReadability {
+    blocks: Vec<BlockReadability>,
+}
+
+impl DocumentReadability {
+    /// Count the features of each block in `doc`.
+    pub fn of_document(doc: &Document) -> Self {
+        let mut builder = DocumentReadabilityBuilder::default();
+        builder.add_document(doc);
+        builder.build()
+    }
+
+    /// Iterate over the counts for each block.
+    ///
+    /// Blocks are ordered by their span in ascending order. Blocks without any
+    /// words are not included.
+    pub fn iter_blocks(&self) -> impl Iterator<Item = &BlockReadability> {
+        self.blocks.iter()
+    }
+
+    /// Get the counts for the whole document.
+    pub fn counts(&self) -> TextCounts {
+        self.blocks
+            .iter()
+            .fold(TextCounts::default(), |counts, block| counts + block.counts)
+    }
+
+    /// Get the readability scores for the whole document.
+    ///
+    /// Returns `None` if the document has no words.
+    pub fn scores(&self) -> Option<Readability> {
+        self.counts().scores()
+    }
+}
+
+/// Build a `DocumentReadability`.
+#[derive(Debug, Default)]
+pub(crate) struct DocumentReadabilityBuilder {
+    blocks: Vec<BlockReadability>,
+}
+
+impl DocumentReadabilityBuilder {
+    /// Count the features of each block in `doc`.
+    pub(crate) fn add_document(&mut self, doc: &Document) {
+        for block in doc.iter() {
+            let Some(span) = block.as_span() else {
+                continue;
+            };
+            let counts = TextCounts::of_block(block);
+            if counts.words > 0 {
+                self.add_block(BlockReadability { span, counts });
+            }
+        }
+    }
+
+    /// Add the counts for a block.
+    pub(crate) fn add_block(&mut self, block: BlockReadability) {
+        self.blocks.push(block);
+    }
+
+    pub(crate) fn build(self) -> DocumentReadability {
+        let mut blocks = self.blocks;
+        blocks.sort_by_key(|block| block.span);
+        DocumentReadability { blocks }
+    }
+}
+
+/// Is `token` a word, rather than punctuation or the second half of a
+/// contraction (e.g. `n't` or `'s`)?
+pub(crate) fn is_word(token: &str) -> bool {
+    token.starts_with(char::is_alphanumeric) && !token.eq_ignore_ascii_case("n't")
+}
+
+/// Estimate the number of syllables in an English word.
+///
+/// This counts the groups of vowels in the word, ignoring common silent
+/// endings. Every word has at least one syllable.
+pub fn count_syllables(word: &str) -> usize {
+    let word = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect::<String>();
+
+    if word.len() <= 3 {
+        return 1;
+    }
+
+    let stem = if let Some(stem) = word.strip_suffix("ed") {
+        if stem.ends_with(['t', 'd']) {
+            &word
+        } else {
+            stem
+        }
+    } else if let Some(stem) = word.strip_suffix("es") {
+        if stem.ends_with(['s', 'z', 'c', 'g', 'x']) || stem.ends_with("sh") || stem.ends_with("ch")
+        {
+            &word
+        } else {
+            stem
+        }
+    } else if word.ends_with('e') && !word.ends_with("le") {
+        &word[..word.len() - 1]
+    } else {
+        &word
+    };
+
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut groups = 0;
+    let mut previous_vowel = false;
+    for c in stem.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            groups += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    groups.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{doc::Document, parsers::MarkdownParser};
+
+    use super::{count_syllables, DocumentReadability, TextCounts};
+
+    fn counts(input: &str) -> TextCounts {
+        let doc = Document::new(&MarkdownParser::default(), input);
+        DocumentReadability::of_document(&doc).counts()
+    }
+
+    #[test]
+    fn test_count_syllables() {
+        for (word, expected) in [
+            ("the", 1),
+            ("cat", 1),
+            ("make", 1),
+            ("makes", 1),
+            ("boxes", 2),
+            ("wanted", 2),
+            ("released", 2),
+            ("table", 2),
+            ("people", 2),
+            ("syllable", 3),
+            ("beautiful", 3),
+            ("readability", 5),
+            ("Readability", 5),
+            ("42", 1),
+        ] {
+            assert_eq!(count_syllables(word), expected, "word={:?}", word);
+        }
+    }
+
+    #[test]
+    fn test_counts() {
+        assert_eq!(counts(""), TextCounts::default());
+        assert_eq!(counts("```\ncode\n```"), TextCounts::default());
+
+        assert_eq!(
+            counts("The cat sat on the mat."),
+            TextCounts {
+                sentences: 1,
+                words: 6,
+                syllables: 6,
+                polysyllables: 0,
+                complex_words: 0,
+                letters: 17,
+            }
+        );
+
+        // Headings, and sentences without a full stop, are still sentences.
+        // Contractions are a single word, and abbreviations don't end a
+        // sentence.
+        assert_eq!(
+            counts("# A title\n\nIt isn't readable, e.g. The end"),
+            TextCounts {
+                sentences: 2,
+                words: 8,
+                syllables: 11,
+                polysyllables: 1,
+                complex_words: 1,
+                letters: 26,
+            }
+        );
+    }
+
+    #[test]
+    fn test_scores() {
+        assert_eq!(counts("").scores(), None);
+
+        let scores = counts("The cat sat on the mat.").scores().unwrap();
+        assert!((scores.flesch_reading_ease - 116.145).abs() < 1e-9);
+        assert!((scores.flesch_kincaid_grade - -1.45).abs() < 1e-9);
+        assert!((scores.gunning_fog - 2.4).abs() < 1e-9);
+        assert!((scores.smog - 3.1291).abs() < 1e-9);
+        assert!((scores.coleman_liau - -4.0733).abs() < 1e-3);
+    }
+}
@@ -1,18 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use serde::Deserialize;
 #[allow(unused_imports)]
 use strum::VariantArray as _;
-use strum_macros::VariantArray;
+use strum_macros::{EnumString, IntoStaticStr, VariantArray};
 use tracing::debug_span;
 
 use crate::{
     block::Word,
-    doc::Document,
+    doc::{Document, DocumentChanges},
     lines::spans_to_ranges,
     matcher::{match_words, Matcher, SingleWordPattern},
     measures::default_measures,
+    readability::{BlockReadability, DocumentReadability, DocumentReadabilityBuilder, Grade},
     rules::default_rules,
     span::ByteSpan,
+    suppress::Suppressions,
     LineCharRange,
 };
 
@@ -45,6 +48,86 @@ impl HasSpan for &Word<'_> {
     }
 }
 
+/// A suggested edit which fixes a warning, by replacing a span of the input.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Suggestion {
+    /// The span of the input to replace.
+    pub span: ByteSpan,
+    /// The replacement text. This is empty if the span should be deleted.
+    pub replacement: String,
+}
+
+impl HasSpan for &Suggestion {
+    fn span(&self) -> ByteSpan {
+        self.span
+    }
+}
+
+/// Apply `suggestions` to `input`, returning the edited string.
+///
+/// Suggestions are applied in order of their spans. A suggestion which
+/// overlaps an earlier suggestion is skipped, so the result is well-defined
+/// even if several warnings suggest edits to the same part of the input.
+pub fn apply_suggestions<'a>(
+    input: &str,
+    suggestions: impl IntoIterator<Item = &'a Suggestion>,
+) -> String {
+    let mut suggestions = suggestions.into_iter().collect::<Vec<_>>();
+    suggestions.sort();
+
+    let mut output = String::with_capacity(input.len());
+    let mut offset = 0;
+
+    for suggestion in suggestions {
+        if suggestion.span.start() < offset {
+            continue;
+        }
+
+        output.push_str(&input[offset..suggestion.span.start()]);
+        output.push_str(&suggestion.replacement);
+        offset = suggestion.span.end();
+    }
+
+    output.push_str(&input[offset..]);
+    output
+}
+
+/// How serious a warning is.
+///
+/// These mirror the diagnostic severities used by editors. Severities are
+/// ordered from most to least serious.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    IntoStaticStr,
+    VariantArray,
+    Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    #[default]
+    Warning,
+    Info,
+    Hint,
+}
+
+impl Severity {
+    /// The name of the severity, e.g. `"warning"`.
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+}
+
 /// A single finding from a rule which indicates a possible error in the
 /// document.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -53,6 +136,18 @@ pub struct Warning {
     pub span: ByteSpan,
     /// The message associated with the warning.
     pub message: String,
+    /// The id of the rule which produced the warning, e.g.
+    /// `passive-construction`.
+    pub rule_id: String,
+    /// The severity of the warning.
+    pub severity: Severity,
+    /// The readability grade of the text covered by the warning, for rules
+    /// which measure readability.
+    pub grade: Option<Grade>,
+    /// Edits which fix the warning, ordered by their span. Alternative edits
+    /// of the same span are ordered from the most to the least likely. This
+    /// is empty if the rule can not suggest a fix.
+    pub suggestions: Vec<Suggestion>,
 }
 
 impl HasSpan for &Warning {
@@ -65,6 +160,8 @@ impl HasSpan for &Warning {
 pub struct WarningBuilder {
     span: ByteSpan,
     message: Option<String>,
+    grade: Option<Grade>,
+    suggestions: Vec<Suggestion>,
 }
 
 impl WarningBuilder {
@@ -75,6 +172,8 @@ impl WarningBuilder {
         WarningBuilder {
             span: words.into(),
             message: None,
+            grade: None,
+            suggestions: Vec::new(),
         }
     }
 
@@ -86,11 +185,34 @@ impl WarningBuilder {
         self
     }
 
+    /// Set the readability grade of the words.
+    pub fn grade(mut self, grade: f64) -> Self {
+        self.grade = Some(Grade::new(grade));
+        self
+    }
+
+    /// Add a suggested edit, which replaces `span` with `replacement`.
+    ///
+    /// Alternative edits of the same span should be added from the most to
+    /// the least likely.
+    pub fn suggestion(mut self, span: ByteSpan, replacement: String) -> Self {
+        self.suggestions.push(Suggestion { span, replacement });
+        self
+    }
+
     /// Build the `Warning`.
+    ///
+    /// The rule id and severity are filled in by `WarningsBuilder::add_warning`.
     pub fn build(self) -> Warning {
+        let mut suggestions = self.suggestions;
+        suggestions.sort_by_key(|suggestion| suggestion.span);
         Warning {
             span: self.span,
             message: self.message.expect("message is required"),
+            rule_id: String::new(),
+            severity: Severity::default(),
+            grade: self.grade,
+            suggestions,
         }
     }
 }
@@ -99,11 +221,29 @@ impl WarningBuilder {
 #[derive(Debug, Default)]
 pub struct WarningsBuilder {
     warnings: Vec<Warning>,
+    rule_id: String,
+    severity: Severity,
 }
 
 impl WarningsBuilder {
-    /// Add a new warning.
+    /// Set the rule id and severity for the warnings added by the next rule.
+    fn start_rule(&mut self, rule_id: &str, severity: Severity) {
+        self.rule_id.clear();
+        self.rule_id.push_str(rule_id);
+        self.severity = severity;
+    }
+
+    /// Add a new warning, from the rule which is currently being applied.
     pub fn add_warning(&mut self, result: Warning) {
+        self.warnings.push(Warning {
+            rule_id: self.rule_id.clone(),
+            severity: self.severity,
+            ..result
+        });
+    }
+
+    /// Add a warning which already has a rule id and severity.
+    fn add_existing_warning(&mut self, result: Warning) {
         self.warnings.push(result);
     }
 
@@ -115,7 +255,20 @@ impl WarningsBuilder {
 }
 
 /// A unique id for a measure.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, VariantArray)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    IntoStaticStr,
+    VariantArray,
+)]
+#[strum(serialize_all = "kebab-case")]
 #[repr(u8)]
 pub enum MeasureKey {
     AbstractNouns,
@@ -125,6 +278,13 @@ pub enum MeasureKey {
     Prepositions,
 }
 
+impl MeasureKey {
+    /// The name of the measure, e.g. `"be-verbs"`.
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+}
+
 #[cfg(test)]
 impl quickcheck::Arbitrary for MeasureKey {
     fn arbitrary(g: &mut quickcheck::Gen) -> Self {
@@ -202,6 +362,7 @@ struct ResultsBuilder<'input> {
     input: &'input str,
     warnings_builder: WarningsBuilder,
     measurements_builder: MeasurementsBuilder<'input>,
+    readability_builder: DocumentReadabilityBuilder,
 }
 
 impl<'input> ResultsBuilder<'input> {
@@ -210,14 +371,24 @@ impl<'input> ResultsBuilder<'input> {
             input,
             warnings_builder: WarningsBuilder::default(),
             measurements_builder: MeasurementsBuilder::default(),
+            readability_builder: DocumentReadabilityBuilder::default(),
         }
     }
 
-    fn build(self) -> Results<'input> {
+    /// Build the results, separating out the warnings which are suppressed.
+    fn build(self, suppressions: &Suppressions) -> Results<'input> {
+        let (suppressed, warnings) = self
+            .warnings_builder
+            .build()
+            .into_iter()
+            .partition(|warning| suppressions.is_suppressed(warning));
+
         Results {
             input: self.input,
-            warnings: self.warnings_builder.build(),
+            warnings,
+            suppressed,
             measurements: self.measurements_builder.build(),
+            readability: self.readability_builder.build(),
         }
     }
 }
@@ -227,7 +398,12 @@ impl<'input> ResultsBuilder<'input> {
 pub struct Results<'input> {
     input: &'input str,
     warnings: Vec<Warning>,
+    /// The warnings which were suppressed by comments in the document. These
+    /// are kept so that `RuleSet::apply_incremental` can restore them if the
+    /// comments are removed.
+    suppressed: Vec<Warning>,
     measurements: Vec<Measurement<'input>>,
+    readability: DocumentReadability,
 }
 
 impl<'input> Results<'input> {
@@ -245,6 +421,14 @@ impl<'input> Results<'input> {
         spans_to_ranges(self.input, self.warnings.iter())
     }
 
+    /// Iterate over the suggestions for `warning` with their ranges.
+    pub fn iter_suggestions_with_ranges<'a>(
+        &'a self,
+        warning: &'a Warning,
+    ) -> impl Iterator<Item = (LineCharRange, &'a Suggestion)> {
+        spans_to_ranges(self.input, warning.suggestions.iter())
+    }
+
     /// Iterate over the measurements.
     ///
     /// Measurements are ordered by the word in ascending order, and then by the `MeasureKey`.
@@ -260,6 +444,20 @@ impl<'input> Results<'input> {
     ) -> impl Iterator<Item = (LineCharRange, &Measurement<'input>)> {
         spans_to_ranges(self.input, self.measurements.iter())
     }
+
+    /// Get the readability of the document and each of its blocks.
+    pub fn readability(&self) -> &DocumentReadability {
+        &self.readability
+    }
+
+    /// Iterate over the readability of each block with their ranges.
+    ///
+    /// Blocks are ordered by their span in ascending order.
+    pub fn iter_block_readability_with_ranges(
+        &self,
+    ) -> impl Iterator<Item = (LineCharRange, &BlockReadability)> {
+        spans_to_ranges(self.input, self.readability.iter_blocks())
+    }
 }
 
 #[cfg(test)]
@@ -280,18 +478,74 @@ impl quickcheck::Arbitrary for Results<'static> {
             builder.measurements_builder.add_measurement(key, &word);
         }
 
-        builder.build()
+        builder.build(&Suppressions::default())
     }
 }
 
+/// The options for a rule, from the rule's table in a config file.
+pub type RuleOptions = toml::Table;
+
+/// Parse the options for a rule into `T`.
+///
+/// `T` should use `#[serde(default, deny_unknown_fields)]`, so that missing
+/// options use their default value and unknown options are an error.
+pub(crate) fn parse_options<T: serde::de::DeserializeOwned>(
+    options: &RuleOptions,
+) -> Result<T, String> {
+    toml::Value::Table(options.clone())
+        .try_into()
+        .map_err(|err: toml::de::Error| err.message().to_string())
+}
+
 /// A rule that finds warnings in a document.
 pub trait Rule: Send + Sync {
+    /// A stable, unique id for the rule, in kebab-case, e.g.
+    /// `passive-construction`.
+    fn id(&self) -> &str;
+
+    /// The severity of warnings from this rule.
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Is the rule on when no config file enables or disables it?
+    ///
+    /// Rules which are off by default are turned on with `enabled = true`.
+    fn is_enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Configure the rule with the options from a config file.
+    ///
+    /// By default, rules have no options, so any option is an error.
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        match options.keys().next() {
+            Some(key) => Err(format!("unknown option {:?}", key)),
+            None => Ok(()),
+        }
+    }
+
+    /// Does the rule compare blocks with each other, rather than checking each
+    /// block on its own?
+    ///
+    /// `RuleSet::apply_incremental` applies these rules to the whole document,
+    /// since an edit to one block can change the warnings in another.
+    fn is_document_wide(&self) -> bool {
+        false
+    }
+
     /// Apply the rule to the document, adding zero or more warnings to the builder.
     fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder);
 }
 
 /// A rule that searches for warnings using a specific pattern, using a `Matcher`.
 pub trait MatcherRule: Send {
+    /// A stable, unique id for the rule. See `Rule::id`.
+    const ID: &'static str;
+
+    /// The severity of warnings from this rule. See `Rule::default_severity`.
+    const SEVERITY: Severity = Severity::Warning;
+
     /// Get the matcher for this rule.
     fn matcher() -> impl Matcher;
 
@@ -304,6 +558,14 @@ pub trait MatcherRule: Send {
 }
 
 impl<U: MatcherRule + Sync> Rule for U {
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn default_severity(&self) -> Severity {
+        Self::SEVERITY
+    }
+
     /// Run the `matcher` on each block in the document, and call `on_match` for
     /// each match.
     fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
@@ -346,14 +608,28 @@ impl MeasureInstance {
     }
 }
 
+struct RuleInstance {
+    rule: Box<dyn Rule>,
+    severity: Severity,
+}
+
+impl RuleInstance {
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        warnings.start_rule(self.rule.id(), self.severity);
+        self.rule.apply(doc, warnings);
+    }
+}
+
 /// A set of rules and measures to apply to a document.
 pub struct RuleSet {
-    rules: Vec<Box<dyn Rule>>,
+    rules: Vec<RuleInstance>,
     measures: Vec<MeasureInstance>,
 }
 
 impl RuleSet {
     /// Create a new rule set with the given rules and masures.
+    ///
+    /// Each rule uses its default severity.
     pub fn new(rules: Vec<Box<dyn Rule>>, measures: Vec<Box<dyn Measure>>) -> Self {
         let measures = measures
             .into_iter()
@@ -362,7 +638,27 @@ impl RuleSet {
                 pattern: measure.pattern(),
             })
             .collect();
-        RuleSet { rules, measures }
+        let mut ruleset = RuleSet {
+            rules: Vec::new(),
+            measures,
+        };
+        ruleset.add_rules(rules);
+        ruleset
+    }
+
+    /// Add a rule to this rule set, overriding its default severity.
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>, severity: Severity) {
+        self.rules.push(RuleInstance { rule, severity });
+    }
+
+    /// Add more rules to this rule set, e.g. rules loaded from a rule file.
+    ///
+    /// Each rule uses its default severity.
+    pub fn add_rules(&mut self, rules: impl IntoIterator<Item = Box<dyn Rule>>) {
+        for rule in rules {
+            let severity = rule.default_severity();
+            self.add_rule(rule, severity);
+        }
     }
 
     /// Apply the rules and measures to the document, returning the results.
@@ -379,7 +675,96 @@ impl RuleSet {
                 measure.apply(doc, &mut results.measurements_builder);
             }
 
-            results.build()
+            results.readability_builder.add_document(doc);
+
+            results.build(doc.suppressions())
+        })
+    }
+}
+
+impl RuleSet {
+    /// Apply the rules and measures to a document created by
+    /// `Document::update`, reusing the results from the previous document.
+    ///
+    /// The rules and measures are only applied to the blocks which changed.
+    /// The results for the other blocks are copied from `previous`, with
+    /// their spans moved to match the new document. Document-wide rules, see
+    /// `Rule::is_document_wide`, are applied to the whole document instead.
+    ///
+    /// `previous` must be the results of applying this rule set to the
+    /// previous document.
+    pub fn apply_incremental<'input>(
+        &self,
+        doc: &Document<'input>,
+        changes: &DocumentChanges,
+        previous: &Results<'_>,
+    ) -> Results<'input> {
+        let apply_span = debug_span!("RuleSet::apply_incremental");
+        apply_span.in_scope(|| {
+            let changed = doc.with_blocks(changes.changed_blocks());
+            let mut results = ResultsBuilder::new(doc.input());
+
+            let mut document_wide = HashSet::new();
+            for rule in &self.rules {
+                if rule.rule.is_document_wide() {
+                    document_wide.insert(rule.rule.id());
+                    rule.apply(doc, &mut results.warnings_builder);
+                } else {
+                    rule.apply(&changed, &mut results.warnings_builder);
+                }
+            }
+
+            for measure in &self.measures {
+                measure.apply(&changed, &mut results.measurements_builder);
+            }
+
+            results.readability_builder.add_document(&changed);
+
+            for warning in previous.warnings.iter().chain(&previous.suppressed) {
+                if document_wide.contains(warning.rule_id.as_str()) {
+                    continue;
+                }
+                if let Some(span) = changes.moved_span(warning.span) {
+                    let suggestions = warning
+                        .suggestions
+                        .iter()
+                        .filter_map(|suggestion| {
+                            Some(Suggestion {
+                                span: changes.moved_span(suggestion.span)?,
+                                replacement: suggestion.replacement.clone(),
+                            })
+                        })
+                        .collect();
+                    results.warnings_builder.add_existing_warning(Warning {
+                        span,
+                        suggestions,
+                        ..warning.clone()
+                    });
+                }
+            }
+
+            for measurement in previous.iter_measurements() {
+                if let Some(span) = changes.moved_span(measurement.word.as_span()) {
+                    let mut word = Word::new(span.as_str(doc.input()), span.start());
+                    if let Some(tag) = measurement.word.tag() {
+                        word.set_tag(tag);
+                    }
+                    results
+                        .measurements_builder
+                        .add_measurement(measurement.key, &word);
+                }
+            }
+
+            for block in previous.readability.iter_blocks() {
+                if let Some(span) = changes.moved_span(block.span) {
+                    results.readability_builder.add_block(BlockReadability {
+                        span,
+                        counts: block.counts,
+                    });
+                }
+            }
+
+            results.build(doc.suppressions())
         })
     }
 }
@@ -387,19 +772,28 @@ impl RuleSet {
 impl Default for RuleSet {
     fn default() -> Self {
         let default_span = debug_span!("RuleSet::default");
-        default_span.in_scope(|| RuleSet::new(default_rules(), default_measures()))
+        default_span.in_scope(|| {
+            let rules = default_rules()
+                .into_iter()
+                .filter(|rule| rule.is_enabled_by_default())
+                .collect();
+            RuleSet::new(rules, default_measures())
+        })
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test {
     use crate::{
-        doc::Document,
-        parsers::PlaintextParser,
+        doc::{Document, Parser},
+        parsers::{MarkdownParser, PlaintextParser},
         rule::{Measure, Results, Rule, RuleSet},
+        ByteSpan,
     };
 
-    use super::WarningBuilder;
+    use std::collections::HashSet;
+
+    use super::{apply_suggestions, Suggestion, WarningBuilder};
 
     pub(crate) fn rule_eq<R: Rule + 'static>(rule: R, input: &str, expected: usize) {
         let doc = Document::new(&PlaintextParser::default(), input);
@@ -415,6 +809,97 @@ pub(crate) mod test {
         );
     }
 
+    /// Apply `rule` to `input`, parsed by `parser`, and get the text and
+    /// message of each warning, and the result of applying every suggestion.
+    fn apply<'input, R: Rule + 'static>(
+        parser: &impl Parser,
+        rule: R,
+        input: &'input str,
+    ) -> (Vec<(&'input str, String)>, String) {
+        let doc = Document::new(parser, input);
+        let ruleset = RuleSet::new(vec![Box::new(rule)], Vec::new());
+        let results = ruleset.apply(&doc);
+        let warnings = results
+            .iter_warnings()
+            .map(|warning| (warning.span.as_str(input), warning.message.clone()))
+            .collect();
+        let suggestions = results
+            .iter_warnings()
+            .flat_map(|warning| warning.suggestions.iter());
+        (warnings, apply_suggestions(input, suggestions))
+    }
+
+    fn spans_eq_with<R: Rule + 'static>(
+        parser: &impl Parser,
+        rule: R,
+        input: &str,
+        expected: &[&str],
+    ) {
+        let (warnings, _) = apply(parser, rule, input);
+        let spans = warnings.iter().map(|(span, _)| *span).collect::<Vec<_>>();
+        assert_eq!(spans, expected, "input={:?}", input);
+    }
+
+    fn messages_eq_with<R: Rule + 'static>(
+        parser: &impl Parser,
+        rule: R,
+        input: &str,
+        expected: &[(&str, &str)],
+    ) {
+        let (warnings, _) = apply(parser, rule, input);
+        let warnings = warnings
+            .iter()
+            .map(|(span, message)| (*span, message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(warnings, expected, "input={:?}", input);
+    }
+
+    /// Apply `rule` to `input`, and check that the warnings cover exactly the
+    /// `expected` text.
+    pub(crate) fn spans_eq<R: Rule + 'static>(rule: R, input: &str, expected: &[&str]) {
+        spans_eq_with(&PlaintextParser::default(), rule, input, expected);
+    }
+
+    /// Like `spans_eq`, but for Markdown `input`.
+    pub(crate) fn markdown_spans_eq<R: Rule + 'static>(rule: R, input: &str, expected: &[&str]) {
+        spans_eq_with(&MarkdownParser::default(), rule, input, expected);
+    }
+
+    /// Apply `rule` to `input`, and check that the warnings have exactly the
+    /// `expected` text and message.
+    pub(crate) fn messages_eq<R: Rule + 'static>(rule: R, input: &str, expected: &[(&str, &str)]) {
+        messages_eq_with(&PlaintextParser::default(), rule, input, expected);
+    }
+
+    /// Like `messages_eq`, but for Markdown `input`.
+    pub(crate) fn markdown_messages_eq<R: Rule + 'static>(
+        rule: R,
+        input: &str,
+        expected: &[(&str, &str)],
+    ) {
+        messages_eq_with(&MarkdownParser::default(), rule, input, expected);
+    }
+
+    /// Apply `rule` to `input`, and check that applying every suggestion gives
+    /// `expected`.
+    pub(crate) fn fix_eq<R: Rule + 'static>(rule: R, input: &str, expected: &str) {
+        let (_, fixed) = apply(&PlaintextParser::default(), rule, input);
+        assert_eq!(fixed, expected);
+    }
+
+    /// Like `fix_eq`, but for Markdown `input`.
+    pub(crate) fn markdown_fix_eq<R: Rule + 'static>(rule: R, input: &str, expected: &str) {
+        let (_, fixed) = apply(&MarkdownParser::default(), rule, input);
+        assert_eq!(fixed, expected);
+    }
+
+    /// Create a default `R`, configured with `options`.
+    pub(crate) fn configured<R: Rule + Default>(options: toml::Table) -> R {
+        let mut rule = R::default();
+        rule.configure(&options).unwrap();
+        rule
+    }
+
     pub(crate) fn measure_eq<M: Measure + 'static>(measure: M, input: &str, expected: usize) {
         let doc = Document::new(&PlaintextParser::default(), input);
         let ruleset = RuleSet::new(Vec::new(), vec![Box::new(measure)]);
@@ -430,6 +915,55 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    fn test_apply_suggestions() {
+        let suggestion = |start, end, replacement: &str| Suggestion {
+            span: ByteSpan::new_unchecked(start, end),
+            replacement: replacement.into(),
+        };
+
+        let input = "It was quite a a complex thing.";
+        assert_eq!(apply_suggestions(input, &[]), input);
+        assert_eq!(
+            apply_suggestions(
+                input,
+                &[
+                    suggestion(14, 16, ""),
+                    suggestion(6, 12, ""),
+                    suggestion(25, 30, "task")
+                ]
+            ),
+            "It was a complex task."
+        );
+
+        // Overlapping suggestions are skipped.
+        assert_eq!(
+            apply_suggestions(input, &[suggestion(3, 12, "is"), suggestion(6, 12, "")]),
+            "It is a a complex thing."
+        );
+
+        // Insertions at the same offset are both applied.
+        assert_eq!(
+            apply_suggestions("ab", &[suggestion(1, 1, "x"), suggestion(1, 1, "y")]),
+            "axyb"
+        );
+    }
+
+    #[test]
+    fn default_rule_ids_are_unique() {
+        let rules = crate::rules::default_rules();
+        let ids = rules.iter().map(|rule| rule.id()).collect::<HashSet<_>>();
+        assert_eq!(ids.len(), rules.len());
+
+        for id in ids {
+            assert!(
+                id.chars().all(|c| c.is_ascii_lowercase() || c == '-'),
+                "{} is not kebab-case",
+                id
+            );
+        }
+    }
+
     #[should_panic]
     #[test]
     fn empty_warnings() {
@@ -0,0 +1,900 @@
+//! Rules loaded from a TOML or YAML rule file.
+//!
+//! A rule file contains a list of rules. Each rule has a unique `id`, a
+//! `pattern`, which is a sequence of patterns to match, an optional list of
+//! words to `ignore` while matching, a `message` template and an optional
+//! `severity` (`error`, `warning`, `info` or `hint`, defaulting to `warning`).
+//! For example, in TOML:
+//!
+//! ```toml
+//! [[rules]]
+//! id = "academic-we"
+//! message = "Academic we: \"{match}\""
+//! pattern = [
+//!     { ignore-case = "we" },
+//!     { tag = "MD" },
+//!     { one-of = [{ tag = "VB" }, { tag = "VBD" }] },
+//! ]
+//! ```
+//!
+//! Or, in YAML:
+//!
+//! ```yaml
+//! rules:
+//!   - id: academic-we
+//!     message: "Academic we: \"{match}\""
+//!     pattern:
+//!       - ignore-case: we
+//!       - tag: MD
+//!       - one-of: [{ tag: VB }, { tag: VBD }]
+//! ```
+//!
+//! Each pattern is one of:
+//!
+//! * a string, which matches a word exactly
+//! * `{ word = "..." }`, which matches a word exactly
+//! * `{ ignore-case = "..." }`, which matches a word ignoring ASCII case
+//! * `{ ends-with-ignore-case = "..." }`, which matches a word ending with an
+//!   ASCII suffix, ignoring case
+//! * `{ tag = "..." }`, which matches a word by its Penn Treebank tag, e.g. `NN`
+//! * `{ opt = <pattern> }`, which optionally matches a pattern
+//! * `{ or = [<pattern>, <pattern>] }`, which matches either of two patterns
+//! * `{ one-of = [<pattern>, ...] }`, which matches any of the patterns
+//! * a list of patterns, which matches the patterns in sequence
+//!
+//! These have the same meaning as the `Matcher` combinators used by the
+//! built-in rules. A rule's `pattern` must always match at least one word, so
+//! it can not be only `opt` patterns. Patterns in `ignore` must match a single
+//! word.
+//!
+//! Set `within-sentence = true` to stop a rule matching across the end of a
+//! sentence.
+//!
+//! The message template can contain `{match}`, which is replaced with the
+//! matched words, and `{0}`, `{1}`, etc, which are replaced with the n'th
+//! matched word. Use `{{` and `}}` for literal braces.
+use std::{collections::HashSet, fmt, fs, io, path::Path, str::FromStr};
+
+use pastelito_model::Tag;
+use serde::Deserialize;
+
+use crate::{
+    block::Word,
+    doc::Document,
+    matcher::{match_words, EndsWithIgnoreCase, Matcher, MultipleWordPattern, SingleWordPattern},
+    rule::{Rule, Severity, WarningBuilder, WarningsBuilder},
+};
+
+/// An error when loading a rule file.
+#[derive(Debug)]
+pub enum RuleFileError {
+    /// The rule file could not be read.
+    Io(io::Error),
+    /// The rule file is not valid TOML, or does not match the expected schema.
+    Toml(toml::de::Error),
+    /// The rule file is not valid YAML, or does not match the expected schema.
+    Yaml(serde_yaml::Error),
+    /// The file extension is not `.toml`, `.yaml` or `.yml`.
+    UnknownFormat,
+    /// A rule in the file is invalid.
+    InvalidRule {
+        /// The index of the rule in the file.
+        index: usize,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for RuleFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleFileError::Io(err) => write!(f, "{}", err),
+            RuleFileError::Toml(err) => write!(f, "{}", err),
+            RuleFileError::Yaml(err) => write!(f, "{}", err),
+            RuleFileError::UnknownFormat => {
+                write!(f, "unknown rule file format, expected .toml, .yaml or .yml")
+            }
+            RuleFileError::InvalidRule { index, message } => {
+                write!(f, "rule {}: {}", index, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RuleFileError::Io(err) => Some(err),
+            RuleFileError::Toml(err) => Some(err),
+            RuleFileError::Yaml(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The top-level structure of a rule file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFileSpec {
+    #[serde(default)]
+    rules: Vec<RuleSpec>,
+}
+
+/// A single rule in a rule file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    id: String,
+    message: String,
+    #[serde(default)]
+    severity: Option<String>,
+    pattern: Vec<PatternSpec>,
+    #[serde(default)]
+    ignore: Vec<PatternSpec>,
+    #[serde(default, rename = "within-sentence")]
+    within_sentence: bool,
+}
+
+/// A pattern in a rule file.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PatternSpec {
+    Literal(String),
+    Sequence(Vec<PatternSpec>),
+    Combinator(CombinatorSpec),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+enum CombinatorSpec {
+    Word(String),
+    IgnoreCase(String),
+    EndsWithIgnoreCase(String),
+    Tag(String),
+    Opt(Box<PatternSpec>),
+    Or(Vec<PatternSpec>),
+    OneOf(Vec<PatternSpec>),
+}
+
+/// A pattern that matches a single word, built from a rule file.
+enum WordPattern {
+    Literal(String),
+    IgnoreCase(String),
+    EndsWithIgnoreCase(EndsWithIgnoreCase),
+    Tag(Tag),
+    OneOf(Vec<WordPattern>),
+}
+
+impl SingleWordPattern for WordPattern {
+    fn matches_word(&self, word: &Word) -> bool {
+        match self {
+            WordPattern::Literal(str) => word.as_str() == str,
+            WordPattern::IgnoreCase(str) => word.as_str().eq_ignore_ascii_case(str),
+            WordPattern::EndsWithIgnoreCase(pattern) => pattern.matches_word(word),
+            WordPattern::Tag(tag) => tag.matches_word(word),
+            WordPattern::OneOf(patterns) => patterns.iter().any(|p| p.matches_word(word)),
+        }
+    }
+}
+
+/// A pattern that matches multiple words, built from a rule file.
+///
+/// This mirrors the static combinators in `matcher`: `Opt`, `Or` and tuples.
+enum Pattern {
+    Word(WordPattern),
+    Opt(Box<Pattern>),
+    OneOf(Vec<Pattern>),
+    Sequence(Vec<Pattern>),
+}
+
+impl Pattern {
+    /// The minimum number of words this pattern can match.
+    fn min_len(&self) -> usize {
+        match self {
+            Pattern::Word(_) => 1,
+            Pattern::Opt(_) => 0,
+            Pattern::OneOf(patterns) => patterns.iter().map(Pattern::min_len).min().unwrap_or(0),
+            Pattern::Sequence(patterns) => patterns.iter().map(Pattern::min_len).sum(),
+        }
+    }
+
+    /// The maximum number of words this pattern can match.
+    fn max_len(&self) -> usize {
+        match self {
+            Pattern::Word(_) => 1,
+            Pattern::Opt(pattern) => pattern.max_len(),
+            Pattern::OneOf(patterns) => patterns.iter().map(Pattern::max_len).max().unwrap_or(0),
+            Pattern::Sequence(patterns) => patterns.iter().map(Pattern::max_len).sum(),
+        }
+    }
+}
+
+impl MultipleWordPattern for Pattern {
+    fn size_hint(&self) -> usize {
+        self.max_len()
+    }
+
+    fn matches<'input>(
+        &self,
+        matched_words: &mut Vec<Word<'input>>,
+        words: &[Word<'input>],
+    ) -> Option<usize> {
+        match self {
+            Pattern::Word(pattern) => pattern.matches(matched_words, words),
+            Pattern::Opt(pattern) => Some(pattern.matches(matched_words, words).unwrap_or(0)),
+            Pattern::OneOf(patterns) => patterns
+                .iter()
+                .find_map(|pattern| pattern.matches(matched_words, words)),
+            Pattern::Sequence(patterns) => {
+                let original_len = matched_words.len();
+                let mut offset = 0;
+
+                for pattern in patterns {
+                    match pattern.matches(matched_words, &words[offset..]) {
+                        Some(next) => offset += next,
+                        None => {
+                            matched_words.truncate(original_len);
+                            return None;
+                        }
+                    }
+                }
+
+                Some(offset)
+            }
+        }
+    }
+}
+
+/// The `Matcher` for a rule loaded from a rule file.
+struct FileMatcher {
+    ignore: Option<WordPattern>,
+    pattern: Pattern,
+    within_sentence: bool,
+}
+
+impl Matcher for FileMatcher {
+    type IgnorePattern = WordPattern;
+    fn ignore_pattern(&self) -> Option<&Self::IgnorePattern> {
+        self.ignore.as_ref()
+    }
+
+    type Pattern = Pattern;
+    fn pattern(&self) -> &Self::Pattern {
+        &self.pattern
+    }
+
+    fn within_sentence(&self) -> bool {
+        self.within_sentence
+    }
+}
+
+/// A part of a message template.
+#[derive(Debug, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Match,
+    Word(usize),
+}
+
+/// A parsed message template.
+#[derive(Debug, PartialEq, Eq)]
+struct Template(Vec<TemplatePart>);
+
+impl Template {
+    /// Parse a template, checking that every `{n}` is less than `max_words`.
+    fn parse(template: &str, max_words: usize) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err("unmatched '{' in message".into()),
+                        }
+                    }
+                    let part = if name == "match" {
+                        TemplatePart::Match
+                    } else if let Ok(index) = name.parse::<usize>() {
+                        if index >= max_words {
+                            return Err(format!(
+                                "message refers to word {{{}}}, but the pattern matches at most {} words",
+                                index, max_words
+                            ));
+                        }
+                        TemplatePart::Word(index)
+                    } else {
+                        return Err(format!("unknown placeholder {{{}}} in message", name));
+                    };
+
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(part);
+                }
+                '}' => return Err("unmatched '}' in message".into()),
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+
+        Ok(Template(parts))
+    }
+
+    /// Render the template for the matched words.
+    fn render(&self, words: &[Word]) -> String {
+        let mut message = String::new();
+        for part in &self.0 {
+            match part {
+                TemplatePart::Text(text) => message.push_str(text),
+                TemplatePart::Match => {
+                    for (i, word) in words.iter().enumerate() {
+                        if i > 0 {
+                            message.push(' ');
+                        }
+                        message.push_str(word.as_str());
+                    }
+                }
+                TemplatePart::Word(index) => {
+                    if let Some(word) = words.get(*index) {
+                        message.push_str(word.as_str());
+                    }
+                }
+            }
+        }
+        message
+    }
+}
+
+/// A rule loaded from a rule file.
+pub struct FileRule {
+    id: String,
+    severity: Severity,
+    matcher: FileMatcher,
+    message: Template,
+}
+
+impl Rule for FileRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        for block in doc.iter() {
+            match_words(block, &self.matcher, |words| {
+                warnings.add_warning(
+                    WarningBuilder::new(words)
+                        .message(self.message.render(words))
+                        .build(),
+                );
+            });
+        }
+    }
+}
+
+/// Compile a pattern which must match a single word.
+fn compile_word(spec: &PatternSpec) -> Result<WordPattern, String> {
+    match compile_pattern(spec)? {
+        Pattern::Word(pattern) => Ok(pattern),
+        _ => Err("patterns in `ignore` must match a single word".into()),
+    }
+}
+
+/// Compile a sequence of patterns.
+fn compile_sequence(specs: &[PatternSpec]) -> Result<Pattern, String> {
+    if specs.is_empty() {
+        return Err("a pattern sequence can not be empty".into());
+    }
+
+    let mut patterns = specs
+        .iter()
+        .map(compile_pattern)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if patterns.len() == 1 {
+        Ok(patterns.remove(0))
+    } else {
+        Ok(Pattern::Sequence(patterns))
+    }
+}
+
+/// Compile a list of alternative patterns.
+fn compile_one_of(specs: &[PatternSpec]) -> Result<Pattern, String> {
+    if specs.is_empty() {
+        return Err("`one-of` needs at least one pattern".into());
+    }
+
+    let patterns = specs
+        .iter()
+        .map(compile_pattern)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Keep single word alternatives as a `WordPattern` so they can be used in
+    // `ignore`.
+    if patterns.iter().all(|p| matches!(p, Pattern::Word(_))) {
+        let words = patterns
+            .into_iter()
+            .map(|p| match p {
+                Pattern::Word(word) => word,
+                _ => unreachable!(),
+            })
+            .collect();
+        Ok(Pattern::Word(WordPattern::OneOf(words)))
+    } else {
+        Ok(Pattern::OneOf(patterns))
+    }
+}
+
+fn compile_pattern(spec: &PatternSpec) -> Result<Pattern, String> {
+    match spec {
+        PatternSpec::Literal(str) | PatternSpec::Combinator(CombinatorSpec::Word(str)) => {
+            Ok(Pattern::Word(WordPattern::Literal(str.clone())))
+        }
+        PatternSpec::Sequence(specs) => compile_sequence(specs),
+        PatternSpec::Combinator(CombinatorSpec::IgnoreCase(str)) => {
+            Ok(Pattern::Word(WordPattern::IgnoreCase(str.clone())))
+        }
+        PatternSpec::Combinator(CombinatorSpec::EndsWithIgnoreCase(suffix)) => {
+            if !suffix.is_ascii() {
+                return Err(format!(
+                    "`ends-with-ignore-case` only supports ASCII suffixes, found {:?}",
+                    suffix
+                ));
+            }
+            Ok(Pattern::Word(WordPattern::EndsWithIgnoreCase(
+                EndsWithIgnoreCase::new(suffix),
+            )))
+        }
+        PatternSpec::Combinator(CombinatorSpec::Tag(name)) => Tag::from_str(name)
+            .map(|tag| Pattern::Word(WordPattern::Tag(tag)))
+            .map_err(|_| format!("unknown tag {:?}", name)),
+        PatternSpec::Combinator(CombinatorSpec::Opt(spec)) => {
+            Ok(Pattern::Opt(Box::new(compile_pattern(spec)?)))
+        }
+        PatternSpec::Combinator(CombinatorSpec::Or(specs)) => {
+            if specs.len() != 2 {
+                return Err(format!(
+                    "`or` needs exactly two patterns, found {}",
+                    specs.len()
+                ));
+            }
+            compile_one_of(specs)
+        }
+        PatternSpec::Combinator(CombinatorSpec::OneOf(specs)) => compile_one_of(specs),
+    }
+}
+
+impl RuleSpec {
+    fn compile(&self) -> Result<FileRule, String> {
+        if self.id.is_empty() {
+            return Err("rule id can not be empty".into());
+        }
+
+        let severity = match &self.severity {
+            Some(name) => Severity::from_str(name).map_err(|_| {
+                format!(
+                    "unknown severity {:?}, expected error, warning, info or hint",
+                    name
+                )
+            })?,
+            None => Severity::default(),
+        };
+
+        let pattern = compile_sequence(&self.pattern)?;
+        if pattern.min_len() == 0 {
+            return Err("the pattern must match at least one word".into());
+        }
+
+        let ignore = match self.ignore.as_slice() {
+            [] => None,
+            [spec] => Some(compile_word(spec)?),
+            specs => Some(WordPattern::OneOf(
+                specs.iter().map(compile_word).collect::<Result<_, _>>()?,
+            )),
+        };
+
+        let message = Template::parse(&self.message, pattern.max_len())?;
+
+        Ok(FileRule {
+            id: self.id.clone(),
+            severity,
+            matcher: FileMatcher {
+                ignore,
+                pattern,
+                within_sentence: self.within_sentence,
+            },
+            message,
+        })
+    }
+}
+
+impl RuleFileSpec {
+    fn compile(self) -> Result<Vec<Box<dyn Rule>>, RuleFileError> {
+        let mut ids = HashSet::new();
+        self.rules
+            .iter()
+            .enumerate()
+            .map(|(index, spec)| {
+                if !ids.insert(spec.id.as_str()) {
+                    return Err(RuleFileError::InvalidRule {
+                        index,
+                        message: format!("duplicate rule id {:?}", spec.id),
+                    });
+                }
+
+                spec.compile()
+                    .map(|rule| Box::new(rule) as Box<dyn Rule>)
+                    .map_err(|message| RuleFileError::InvalidRule { index, message })
+            })
+            .collect()
+    }
+}
+
+/// Load the rules from a TOML rule file.
+pub fn from_toml_str(input: &str) -> Result<Vec<Box<dyn Rule>>, RuleFileError> {
+    toml::from_str::<RuleFileSpec>(input)
+        .map_err(RuleFileError::Toml)?
+        .compile()
+}
+
+/// Load the rules from a YAML rule file.
+pub fn from_yaml_str(input: &str) -> Result<Vec<Box<dyn Rule>>, RuleFileError> {
+    serde_yaml::from_str::<RuleFileSpec>(input)
+        .map_err(RuleFileError::Yaml)?
+        .compile()
+}
+
+/// Load the rules from a rule file.
+///
+/// The format is chosen by the file extension: `.toml`, or `.yaml` or `.yml`.
+pub fn from_path(path: &Path) -> Result<Vec<Box<dyn Rule>>, RuleFileError> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    let load = match extension.as_deref() {
+        Some("toml") => from_toml_str,
+        Some("yaml") | Some("yml") => from_yaml_str,
+        _ => return Err(RuleFileError::UnknownFormat),
+    };
+
+    let input = fs::read_to_string(path).map_err(RuleFileError::Io)?;
+    load(&input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::Document,
+        parsers::PlaintextParser,
+        rule::{RuleSet, Severity},
+    };
+
+    use super::{from_toml_str, from_yaml_str, RuleFileError, Template, TemplatePart};
+
+    fn messages(
+        rules: Result<Vec<Box<dyn crate::rule::Rule>>, RuleFileError>,
+        input: &str,
+    ) -> Vec<String> {
+        let doc = Document::new(&PlaintextParser::default(), input);
+        let ruleset = RuleSet::new(rules.unwrap(), Vec::new());
+        ruleset
+            .apply(&doc)
+            .iter_warnings()
+            .map(|warning| warning.message.clone())
+            .collect()
+    }
+
+    fn toml_eq(rules: &str, input: &str, expected: &[&str]) {
+        assert_eq!(messages(from_toml_str(rules), input), expected);
+    }
+
+    fn invalid(rules: &str) -> String {
+        match from_toml_str(rules) {
+            Ok(_) => panic!("expected an error"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_literal() {
+        let rules = r#"
+            [[rules]]
+            id = "utilize"
+            message = "Prefer 'use' over '{match}'"
+            pattern = ["utilize"]
+        "#;
+        toml_eq(rules, "We utilize it.", &["Prefer 'use' over 'utilize'"]);
+        toml_eq(rules, "Utilize it.", &[]);
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        let rules = r#"
+            [[rules]]
+            id = "utilize"
+            message = "{0}"
+            pattern = [{ ignore-case = "utilize" }]
+        "#;
+        toml_eq(rules, "Utilize it and utilize it.", &["Utilize", "utilize"]);
+    }
+
+    #[test]
+    fn test_ends_with_ignore_case() {
+        let rules = r#"
+            [[rules]]
+            id = "ly-adverbs"
+            message = "{0}"
+            pattern = [{ ends-with-ignore-case = "LY" }]
+        "#;
+        toml_eq(rules, "It was really quickly done.", &["really", "quickly"]);
+    }
+
+    #[test]
+    fn test_tag() {
+        let rules = r#"
+            [[rules]]
+            id = "determiner-noun"
+            message = "{match}"
+            pattern = [{ tag = "DT" }, { tag = "NN" }]
+        "#;
+        toml_eq(rules, "The cat sat on the mat.", &["The cat", "the mat"]);
+    }
+
+    #[test]
+    fn test_opt() {
+        let rules = r#"
+            [[rules]]
+            id = "the-big"
+            message = "{match}"
+            pattern = [{ ignore-case = "the" }, { opt = "very" }, "big"]
+        "#;
+        toml_eq(
+            rules,
+            "The big cat saw the very big dog.",
+            &["The big", "the very big"],
+        );
+    }
+
+    #[test]
+    fn test_or_and_one_of() {
+        let rules = r#"
+            [[rules]]
+            id = "cat-or-big-dog"
+            message = "{match}"
+            pattern = [{ or = ["cat", ["big", "dog"]] }]
+
+            [[rules]]
+            id = "verbs"
+            message = "{match}"
+            pattern = [{ one-of = ["sat", "saw", "ran"] }]
+        "#;
+        toml_eq(
+            rules,
+            "The cat sat and the big dog ran.",
+            &["cat", "sat", "big dog", "ran"],
+        );
+    }
+
+    #[test]
+    fn test_ignore() {
+        let rules = r#"
+            [[rules]]
+            id = "adjective-pairs"
+            message = "{0} {1}"
+            ignore = [{ tag = "," }, { tag = "CC" }]
+            pattern = [{ tag = "JJ" }, { tag = "JJ" }]
+        "#;
+        toml_eq(
+            rules,
+            "The big, green and old mat.",
+            &["big green", "green old"],
+        );
+    }
+
+    #[test]
+    fn test_within_sentence() {
+        let rules = r#"
+            [[rules]]
+            id = "end-then-the"
+            message = "{match}"
+            pattern = [{ tag = "NN" }, ".", "The"]
+        "#;
+        toml_eq(rules, "The cat. The mat.", &["cat . The"]);
+
+        let rules = r#"
+            [[rules]]
+            id = "end-then-the"
+            message = "{match}"
+            pattern = [{ tag = "NN" }, ".", "The"]
+            within-sentence = true
+        "#;
+        toml_eq(rules, "The cat. The mat.", &[]);
+    }
+
+    #[test]
+    fn test_yaml() {
+        let rules = r#"
+rules:
+  - id: utilize
+    message: "Prefer 'use' over '{match}'"
+    pattern:
+      - ignore-case: utilize
+  - id: modal-verb
+    message: "{1}"
+    ignore:
+      - tag: RB
+    pattern: [{ tag: MD }, { one-of: [{ tag: VB }, { tag: VBD }] }]
+"#;
+        assert_eq!(
+            messages(
+                from_yaml_str(rules),
+                "We utilize it. We will quickly show it."
+            ),
+            vec!["Prefer 'use' over 'utilize'", "show"]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            pattern = [{ tag = "XYZ" }]"#
+        )
+        .contains("unknown tag \"XYZ\""));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            pattern = [{ or = ["a", "b", "c"] }]"#
+        )
+        .contains("exactly two"));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            ignore = [["a", "b"]]
+            pattern = ["c"]"#
+        )
+        .contains("single word"));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "{2}"
+            pattern = ["a", { opt = "b" }]"#
+        )
+        .contains("at most 2 words"));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            pattern = [{ opt = "very" }]"#
+        )
+        .contains("at least one word"));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            pattern = [{ opt = "very" }, { one-of = ["big", { opt = "small" }] }]"#
+        )
+        .contains("at least one word"));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            pattern = [{ ends-with-ignore-case = "é" }]"#
+        )
+        .contains("ASCII"));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            pattern = [{ unknown = "a" }]"#
+        )
+        .starts_with("TOML parse error"));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            severity = "fatal"
+            message = "x"
+            pattern = ["a"]"#
+        )
+        .contains("unknown severity \"fatal\""));
+
+        assert!(invalid(
+            r#"[[rules]]
+            id = "invalid"
+            message = "x"
+            pattern = ["a"]
+
+            [[rules]]
+            id = "invalid"
+            message = "y"
+            pattern = ["b"]"#
+        )
+        .starts_with("rule 1: duplicate rule id"));
+    }
+
+    #[test]
+    fn test_id_and_severity() {
+        let rules = from_toml_str(
+            r#"
+            [[rules]]
+            id = "utilize"
+            severity = "hint"
+            message = "x"
+            pattern = ["utilize"]
+
+            [[rules]]
+            id = "leverage"
+            message = "y"
+            pattern = ["leverage"]
+        "#,
+        )
+        .unwrap();
+
+        let doc = Document::new(&PlaintextParser::default(), "We utilize and leverage it.");
+        let ruleset = RuleSet::new(rules, Vec::new());
+        let results = ruleset.apply(&doc);
+        let warnings = results
+            .iter_warnings()
+            .map(|warning| (warning.rule_id.as_str(), warning.severity))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            warnings,
+            vec![("utilize", Severity::Hint), ("leverage", Severity::Warning)]
+        );
+    }
+
+    #[test]
+    fn test_template() {
+        assert_eq!(
+            Template::parse("{{literal}} {match}: {1}", 2),
+            Ok(Template(vec![
+                TemplatePart::Text("{literal} ".into()),
+                TemplatePart::Match,
+                TemplatePart::Text(": ".into()),
+                TemplatePart::Word(1),
+            ]))
+        );
+        assert!(Template::parse("{nope}", 1).is_err());
+        assert!(Template::parse("}", 1).is_err());
+        assert_eq!(
+            Template::parse("Found {match", 1),
+            Err("unmatched '{' in message".to_string())
+        );
+        assert!(Template::parse("{", 1).is_err());
+    }
+}
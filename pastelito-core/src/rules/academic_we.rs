@@ -9,6 +9,8 @@ use crate::{
 pub struct AcademicWe;
 
 impl MatcherRule for AcademicWe {
+    const ID: &'static str = "academic-we";
+
     fn matcher() -> impl Matcher {
         (
             IgnoreCase::new("we"),
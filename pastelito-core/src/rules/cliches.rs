@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::{
+    phrases::{match_case, Phrases},
+    rule::{parse_options, Rule, RuleOptions, WarningBuilder, WarningsBuilder},
+    ByteSpan, Document,
+};
+
+/// The bundled list of clichés and redundant pairs.
+static CLICHES: &str = include_str!("data/cliches.toml");
+
+/// A list of clichés and redundant pairs, used for both the bundled list and
+/// the rule options.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct Options {
+    /// Clichés, which have no replacement.
+    cliches: Vec<String>,
+    /// Redundant pairs, mapped to the concise word to keep.
+    redundant_pairs: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug)]
+enum Entry {
+    Cliche,
+    /// A redundant pair, with its replacement.
+    RedundantPair(String),
+}
+
+/// Flag clichés, e.g. "low-hanging fruit", and redundant pairs, e.g. "end
+/// result".
+///
+/// Redundant pairs have a suggested replacement. Both lists come from a bundled
+/// list, which can be extended with the `cliches` and `redundant-pairs`
+/// options.
+#[derive(Clone, Debug)]
+pub struct Cliches {
+    phrases: Phrases<Entry>,
+}
+
+impl Cliches {
+    /// Add the phrases in `options`, replacing any existing phrases.
+    fn extend(&mut self, options: Options) -> Result<(), String> {
+        for cliche in options.cliches {
+            self.phrases.insert(&cliche, Entry::Cliche);
+        }
+        for (phrase, replacement) in options.redundant_pairs {
+            if replacement.trim().is_empty() {
+                return Err(format!("empty replacement for {:?}", phrase));
+            }
+            self.phrases
+                .insert(&phrase, Entry::RedundantPair(replacement));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Cliches {
+    fn default() -> Self {
+        let bundled: Options =
+            toml::from_str(CLICHES).expect("Internal error: invalid cliches.toml");
+
+        let mut rule = Cliches {
+            phrases: Phrases::default(),
+        };
+        rule.extend(bundled)
+            .expect("Internal error: invalid cliches.toml");
+        rule
+    }
+}
+
+impl Rule for Cliches {
+    fn id(&self) -> &str {
+        "cliches"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        self.extend(options)
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        for block in doc.iter() {
+            for (words, entry) in self.phrases.find_all(block.as_slice()) {
+                let span: ByteSpan = words.into();
+                let matched = span.as_str(doc.input());
+                let warning = match entry {
+                    Entry::Cliche => WarningBuilder::new(words).message("Cliché".into()),
+                    Entry::RedundantPair(replacement) => WarningBuilder::new(words)
+                        .message(format!("Redundant pair, prefer \"{}\"", replacement))
+                        .suggestion(span, match_case(matched, replacement)),
+                };
+                warnings.add_warning(warning.build());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::{
+        test::{fix_eq, spans_eq},
+        Rule,
+    };
+
+    use super::{Cliches, Options, CLICHES};
+
+    #[test]
+    fn test() {
+        spans_eq(
+            Cliches::default(),
+            "At the end of the day, it was low-hanging fruit.",
+            &["At the end of the day", "low-hanging fruit"],
+        );
+        spans_eq(
+            Cliches::default(),
+            "The end result was a free gift.",
+            &["end result", "free gift"],
+        );
+        spans_eq(Cliches::default(), "The result was a gift.", &[]);
+        // Contractions are split into several words.
+        spans_eq(
+            Cliches::default(),
+            "You can't see the forest for the trees.",
+            &["can't see the forest for the trees"],
+        );
+        spans_eq(
+            Cliches::default(),
+            "Don't rock the boat.",
+            &["Don't rock the boat"],
+        );
+    }
+
+    #[test]
+    fn test_fix() {
+        fix_eq(
+            Cliches::default(),
+            "It is absolutely essential.",
+            "It is essential.",
+        );
+        fix_eq(
+            Cliches::default(),
+            "Past history shows the end result.",
+            "History shows the result.",
+        );
+        // Clichés have no replacement.
+        fix_eq(
+            Cliches::default(),
+            "It was low-hanging fruit.",
+            "It was low-hanging fruit.",
+        );
+    }
+
+    /// Every bundled phrase matches itself.
+    #[test]
+    fn test_bundled() {
+        let bundled: Options = toml::from_str(CLICHES).unwrap();
+        let rule = Cliches::default();
+        for phrase in bundled.cliches.iter().chain(bundled.redundant_pairs.keys()) {
+            spans_eq(rule.clone(), phrase, &[phrase]);
+        }
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut rule = Cliches::default();
+        rule.configure(&toml::toml! {
+            cliches = ["move fast and break things"]
+
+            [redundant-pairs]
+            "true fact" = "fact"
+        })
+        .unwrap();
+        spans_eq(
+            rule.clone(),
+            "Move fast and break things, it is a true fact.",
+            &["Move fast and break things", "true fact"],
+        );
+
+        assert!(rule
+            .configure(&toml::toml! {
+                [redundant-pairs]
+                "true fact" = " "
+            })
+            .unwrap_err()
+            .contains("empty replacement"));
+        assert!(rule
+            .configure(&toml::toml! { phrases = [] })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+}
@@ -0,0 +1,202 @@
+use pastelito_model::Tag;
+
+use crate::{
+    matcher::{
+        match_words, IgnoreCase, Matcher, OneOfS, Opt, OrS, SingleWordPattern, WithinSentence,
+    },
+    rule::{Rule, WarningBuilder, WarningsBuilder},
+    Document, Word,
+};
+
+/// The maximum number of words between "it is" and "that" in a cleft
+/// sentence.
+const MAX_CLEFT_GAP: usize = 4;
+
+/// A form of "be".
+fn be() -> impl SingleWordPattern {
+    OneOfS([
+        IgnoreCase::new("is"),
+        IgnoreCase::new("are"),
+        IgnoreCase::new("was"),
+        IgnoreCase::new("were"),
+        IgnoreCase::new("be"),
+        IgnoreCase::new("been"),
+        IgnoreCase::new("'s"),
+        IgnoreCase::new("'re"),
+    ])
+}
+
+/// Flag expletive constructions, which delay the subject of a sentence.
+///
+/// These are "there" followed by a form of "be", e.g. "There are many reasons
+/// that...", and cleft sentences, e.g. "It is clear that...".
+///
+/// `Expletives` is not a `MatcherRule`, as a cleft sentence can have several
+/// words between "it is" and "that".
+pub struct Expletives;
+
+impl Expletives {
+    /// "there", followed by a form of "be", e.g. "there is", "there will be" or
+    /// "there has been".
+    fn there_matcher() -> impl Matcher {
+        WithinSentence((
+            Tag::ExistentialThere,
+            Opt(OrS(
+                Tag::Modal,
+                OneOfS([
+                    IgnoreCase::new("has"),
+                    IgnoreCase::new("have"),
+                    IgnoreCase::new("had"),
+                ]),
+            )),
+            be(),
+        ))
+    }
+
+    /// Find the cleft sentences in `sentence`, such as "it is clear that", and
+    /// call `on_match` with the words from "it" to "that".
+    fn find_clefts<'input>(sentence: &[Word<'input>], mut on_match: impl FnMut(&[Word<'input>])) {
+        let be = be();
+        let mut i = 0;
+        while i + 1 < sentence.len() {
+            let is_it = sentence[i].as_str().eq_ignore_ascii_case("it");
+            if is_it && be.matches_word(&sentence[i + 1]) {
+                if let Some(len) = Self::cleft_len(&sentence[i..]) {
+                    on_match(&sentence[i..i + len]);
+                    i += len;
+                    continue;
+                }
+            }
+            i += 1;
+        }
+    }
+
+    /// If `words` starts with a cleft sentence, get the number of words up to
+    /// and including "that" or "who".
+    ///
+    /// The words between "it is" and "that" must not contain a verb or
+    /// punctuation. "so" or "such" before "that" start a result clause rather
+    /// than a cleft, e.g. "it was so big that...".
+    fn cleft_len(words: &[Word]) -> Option<usize> {
+        for (gap, word) in words.iter().skip(2).take(MAX_CLEFT_GAP + 1).enumerate() {
+            let str = word.as_str();
+            if str.eq_ignore_ascii_case("that") || str.eq_ignore_ascii_case("who") {
+                return if gap > 0 { Some(gap + 3) } else { None };
+            }
+
+            if str.eq_ignore_ascii_case("so")
+                || str.eq_ignore_ascii_case("such")
+                || !is_cleft_gap_word(word)
+            {
+                return None;
+            }
+        }
+        None
+    }
+}
+
+/// Can `word` be between "it is" and "that" in a cleft sentence?
+fn is_cleft_gap_word(word: &Word) -> bool {
+    !matches!(
+        word.tag(),
+        None | Some(
+            Tag::VerbBaseForm
+                | Tag::VerbPastTense
+                | Tag::VerbGerundOrPresentParticiple
+                | Tag::VerbNon3rdPersonSingularPresent
+                | Tag::Verb3rdPersonSingularPresent
+                | Tag::Modal
+                | Tag::Comma
+                | Tag::EndOfSentence
+                | Tag::Colon
+                | Tag::Open
+                | Tag::Close
+        )
+    )
+}
+
+impl Rule for Expletives {
+    fn id(&self) -> &str {
+        "expletives"
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let matcher = Self::there_matcher();
+        for block in doc.iter() {
+            let mut add_warning = |words: &[Word]| {
+                warnings.add_warning(
+                    WarningBuilder::new(words)
+                        .message("Expletive construction".into())
+                        .build(),
+                );
+            };
+
+            match_words(block, &matcher, &mut add_warning);
+            for sentence in block.sentences() {
+                Self::find_clefts(sentence, &mut add_warning);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::test::spans_eq;
+
+    use super::Expletives;
+
+    #[test]
+    fn test_there() {
+        spans_eq(
+            Expletives,
+            "There are many reasons that we left.",
+            &["There are"],
+        );
+        spans_eq(Expletives, "There was a cat.", &["There was"]);
+        spans_eq(Expletives, "There's a cat.", &["There's"]);
+        spans_eq(Expletives, "There will be cake.", &["There will be"]);
+        spans_eq(Expletives, "There has been a mistake.", &["There has been"]);
+        spans_eq(Expletives, "We knew there were mistakes.", &["there were"]);
+        spans_eq(Expletives, "The cat sat there.", &[]);
+        spans_eq(Expletives, "The cat sat there. Is it big?", &[]);
+    }
+
+    #[test]
+    fn test_cleft() {
+        spans_eq(
+            Expletives,
+            "It is important that we leave.",
+            &["It is important that"],
+        );
+        spans_eq(
+            Expletives,
+            "It was the dog that ate it.",
+            &["It was the dog that"],
+        );
+        spans_eq(Expletives, "It was John who left.", &["It was John who"]);
+        spans_eq(
+            Expletives,
+            "I think it is clear that we left.",
+            &["it is clear that"],
+        );
+        spans_eq(Expletives, "It is big.", &[]);
+        spans_eq(Expletives, "It is that simple.", &[]);
+        spans_eq(Expletives, "It was so big that it broke.", &[]);
+        spans_eq(Expletives, "It is big, and that is good.", &[]);
+        spans_eq(Expletives, "It is big. That is good.", &[]);
+        spans_eq(
+            Expletives,
+            "It is a very big and very green cat that sat.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_both() {
+        spans_eq(
+            Expletives,
+            "There are cats. It is clear that they sat.",
+            &["There are", "It is clear that"],
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use serde::Deserialize;
+
+use crate::{
+    readability::TextCounts,
+    rule::{parse_options, Rule, RuleOptions, Severity, WarningBuilder, WarningsBuilder},
+    Document,
+};
+
+/// Sentences with fewer words than this are never flagged. The grade of a
+/// short sentence depends too much on a few long words.
+const MIN_WORDS: usize = 14;
+
+/// Flag sentences with a high Flesch-Kincaid grade level.
+///
+/// Each warning covers the whole sentence and carries its grade, so editors
+/// can shade hard and very hard sentences differently.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct HardSentences {
+    /// Sentences with at least this grade are hard to read.
+    hard_grade: f64,
+    /// Sentences with at least this grade are very hard to read.
+    very_hard_grade: f64,
+}
+
+impl Default for HardSentences {
+    fn default() -> Self {
+        HardSentences {
+            hard_grade: 10.0,
+            very_hard_grade: 14.0,
+        }
+    }
+}
+
+impl Rule for HardSentences {
+    fn id(&self) -> &str {
+        "hard-sentences"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let configured: HardSentences = parse_options(options)?;
+        if configured.hard_grade > configured.very_hard_grade {
+            return Err("hard-grade must not be greater than very-hard-grade".into());
+        }
+        *self = configured;
+        Ok(())
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        for block in doc.iter() {
+            for sentence in block.sentences() {
+                let counts = TextCounts::of_sentence(sentence);
+                if counts.words < MIN_WORDS {
+                    continue;
+                }
+                let Some(scores) = counts.scores() else {
+                    continue;
+                };
+
+                let grade = scores.flesch_kincaid_grade;
+                let message = if grade >= self.very_hard_grade {
+                    "Very hard to read"
+                } else if grade >= self.hard_grade {
+                    "Hard to read"
+                } else {
+                    continue;
+                };
+
+                warnings.add_warning(
+                    WarningBuilder::new(sentence)
+                        .message(format!("{} (grade {:.1})", message, grade))
+                        .grade(grade)
+                        .build(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::Document,
+        parsers::PlaintextParser,
+        rule::{
+            test::{configured, messages_eq},
+            Rule, RuleSet,
+        },
+    };
+
+    use super::HardSentences;
+
+    const SIMPLE: &str =
+        "The cat sat on the mat and the dog sat on the bed and the bird sat on the tree.";
+    const COMPLEX: &str = "The committee considered several complicated proposals regarding the \
+                           administration of educational institutions throughout the region.";
+
+    #[test]
+    fn test_simple() {
+        messages_eq(HardSentences::default(), SIMPLE, &[]);
+    }
+
+    #[test]
+    fn test_complex() {
+        let input = format!("{} {}", SIMPLE, COMPLEX);
+        messages_eq(
+            HardSentences::default(),
+            &input,
+            &[(COMPLEX, "Very hard to read (grade 22.5)")],
+        );
+
+        let doc = Document::new(&PlaintextParser::default(), COMPLEX);
+        let results =
+            RuleSet::new(vec![Box::new(HardSentences::default())], Vec::new()).apply(&doc);
+        let grade = results.iter_warnings().next().unwrap().grade.unwrap();
+        assert!((grade.value() - 22.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_short() {
+        messages_eq(
+            HardSentences::default(),
+            "Complicated administrative institutions proliferated.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_configure() {
+        let rule: HardSentences = configured(toml::toml! {
+            hard-grade = 3
+            very-hard-grade = 30
+        });
+        messages_eq(rule, SIMPLE, &[(SIMPLE, "Hard to read (grade 4.0)")]);
+        messages_eq(rule, COMPLEX, &[(COMPLEX, "Hard to read (grade 22.5)")]);
+
+        let mut rule = HardSentences::default();
+        assert!(rule
+            .configure(&toml::toml! { hard-grade = 20 })
+            .unwrap_err()
+            .contains("very-hard-grade"));
+        assert!(rule
+            .configure(&toml::toml! { grade = 20 })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+}
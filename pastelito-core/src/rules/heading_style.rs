@@ -0,0 +1,528 @@
+use pastelito_model::Tag;
+use serde::Deserialize;
+
+use crate::{
+    block::BlockKind,
+    phrases::Phrases,
+    readability::is_word,
+    rule::{parse_options, Rule, RuleOptions, Severity, WarningBuilder, WarningsBuilder},
+    Block, ByteSpan, Document, Word,
+};
+
+/// Words which are lowercase in title case, unless they are the first or last
+/// word of a heading. These are articles, conjunctions, and prepositions of
+/// four letters or fewer.
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "if", "in", "into", "nor", "of",
+    "off", "on", "onto", "or", "over", "per", "so", "the", "to", "up", "upon", "via", "vs", "with",
+    "yet",
+];
+
+/// Punctuation which headings should not end with. Question marks are
+/// allowed.
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ':', ';', '!'];
+
+/// The capitalization style of headings.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Case {
+    /// Only the first word, and proper nouns, are capitalized, e.g. "Getting
+    /// started with Pastelito".
+    #[default]
+    Sentence,
+    /// Each word is capitalized, except minor words, e.g. "Getting Started
+    /// with Pastelito".
+    Title,
+}
+
+impl Case {
+    fn as_str(self) -> &'static str {
+        match self {
+            Case::Sentence => "sentence case",
+            Case::Title => "title case",
+        }
+    }
+}
+
+/// The options for `HeadingStyle`.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct Options {
+    case: Case,
+    exceptions: Vec<String>,
+    max_words: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            case: Case::default(),
+            exceptions: Vec::new(),
+            max_words: 10,
+        }
+    }
+}
+
+/// How a heading starts, for comparing sibling headings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum VerbForm {
+    /// A gerund, e.g. "Installing Pastelito".
+    Gerund,
+    /// An imperative, e.g. "Install Pastelito".
+    Imperative,
+}
+
+impl VerbForm {
+    /// The verb form of `word`, if it is the first word of a heading.
+    fn of(word: &Word) -> Option<Self> {
+        match word.tag()? {
+            Tag::VerbGerundOrPresentParticiple => Some(VerbForm::Gerund),
+            Tag::VerbBaseForm | Tag::VerbNon3rdPersonSingularPresent => Some(VerbForm::Imperative),
+            // Capitalized words in headings are often tagged as nouns, so
+            // "Installing" may not be tagged as a gerund.
+            Tag::ProperNounSingular | Tag::NounSingularOrMass
+                if word.as_str().len() > 4 && word.as_str().to_lowercase().ends_with("ing") =>
+            {
+                Some(VerbForm::Gerund)
+            }
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            VerbForm::Gerund => "a gerund",
+            VerbForm::Imperative => "an imperative",
+        }
+    }
+}
+
+/// Flag headings which don't follow a consistent style.
+///
+/// Headings should use sentence case or title case, set by the `case` option,
+/// and should not end with punctuation or be longer than `max-words` words.
+/// The `exceptions` option lists proper nouns, which are always written as
+/// given. Sibling headings, which have the same level within the same
+/// section, should start with the same verb form, e.g. "Installing" and
+/// "Configuring" rather than "Installing" and "Configure".
+///
+/// The rule is off by default, since capitalized words in headings are often
+/// tagged as proper nouns, so proper nouns such as "Linux" can't be told apart
+/// from words which should be lowercase.
+pub struct HeadingStyle {
+    case: Case,
+    /// Proper nouns, each mapped to the way it is written.
+    exceptions: Phrases<String>,
+    max_words: usize,
+}
+
+impl Default for HeadingStyle {
+    fn default() -> Self {
+        HeadingStyle::new(Options::default())
+    }
+}
+
+impl HeadingStyle {
+    fn new(options: Options) -> Self {
+        let mut exceptions = Phrases::default();
+        for exception in options.exceptions {
+            exceptions.insert(&exception, exception.clone());
+        }
+        HeadingStyle {
+            case: options.case,
+            exceptions,
+            max_words: options.max_words,
+        }
+    }
+
+    /// Flag exceptions written in the wrong case, and words which don't match
+    /// the case style.
+    fn check_case(&self, words: &[Word], input: &str, warnings: &mut WarningsBuilder) {
+        let mut is_exception = vec![false; words.len()];
+        for (matched, exception) in self.exceptions.find_all(words) {
+            let start = words
+                .iter()
+                .position(|word| word.as_offset() == matched[0].as_offset())
+                .unwrap_or_default();
+            is_exception[start..start + matched.len()].fill(true);
+
+            let span: ByteSpan = matched.into();
+            let text = span.as_str(input);
+            if text != exception {
+                warnings.add_warning(
+                    WarningBuilder::new(matched)
+                        .message(format!("\"{}\" should be \"{}\"", text, exception))
+                        .suggestion(span, exception.clone())
+                        .build(),
+                );
+            }
+        }
+
+        let indices = (0..words.len())
+            .filter(|i| is_word(words[*i].as_str()))
+            .collect::<Vec<_>>();
+        // The first word is the first alphabetic word, so "Getting" is first
+        // in "1. Getting started".
+        let first = indices
+            .iter()
+            .position(|&i| words[i].as_str().starts_with(char::is_alphabetic));
+        for (n, &i) in indices.iter().enumerate() {
+            let word = &words[i];
+            let str = word.as_str();
+            if is_exception[i] || !str.starts_with(char::is_alphabetic) || has_inner_capitals(str) {
+                continue;
+            }
+
+            // The first word after a colon starts a new phrase.
+            let is_first = Some(n) == first || (i > 0 && words[i - 1].as_str() == ":");
+            let is_last = n == indices.len() - 1;
+            let is_capitalized = str.starts_with(char::is_uppercase);
+
+            let expected = match self.case {
+                Case::Sentence if is_first => (!is_capitalized).then(|| capitalize(str)),
+                Case::Sentence => (is_capitalized && str != "I").then(|| str.to_lowercase()),
+                Case::Title => {
+                    let is_minor = MINOR_WORDS
+                        .iter()
+                        .any(|minor| minor.eq_ignore_ascii_case(str));
+                    if is_minor && !is_first && !is_last {
+                        is_capitalized.then(|| str.to_lowercase())
+                    } else {
+                        (!is_capitalized).then(|| capitalize(str))
+                    }
+                }
+            };
+
+            if let Some(expected) = expected {
+                warnings.add_warning(
+                    WarningBuilder::new(std::slice::from_ref(word))
+                        .message(format!(
+                            "\"{}\" should be \"{}\" in {}",
+                            str,
+                            expected,
+                            self.case.as_str()
+                        ))
+                        .suggestion(word.as_span(), expected)
+                        .build(),
+                );
+            }
+        }
+    }
+
+    /// Flag a heading which ends with punctuation.
+    fn check_punctuation(&self, words: &[Word], warnings: &mut WarningsBuilder) {
+        let [.., previous, last] = words else {
+            return;
+        };
+        if !last
+            .as_str()
+            .chars()
+            .all(|c| TRAILING_PUNCTUATION.contains(&c))
+        {
+            return;
+        }
+        warnings.add_warning(
+            WarningBuilder::new(std::slice::from_ref(last))
+                .message(format!(
+                    "Headings should not end with \"{}\"",
+                    last.as_str()
+                ))
+                .suggestion(
+                    ByteSpan::new_unchecked(previous.as_span().end(), last.as_span().end()),
+                    String::new(),
+                )
+                .build(),
+        );
+    }
+
+    /// Flag a heading with more than `max_words` words.
+    fn check_length(&self, words: &[Word], warnings: &mut WarningsBuilder) {
+        let count = words.iter().filter(|word| is_word(word.as_str())).count();
+        if count > self.max_words {
+            warnings.add_warning(
+                WarningBuilder::new(words)
+                    .message(format!(
+                        "Heading has {} words; keep headings to {} words or fewer",
+                        count, self.max_words
+                    ))
+                    .build(),
+            );
+        }
+    }
+
+    /// Flag sibling headings which start with a different verb form to the
+    /// first sibling heading which starts with a verb.
+    fn check_siblings(&self, siblings: &[&[Word]], warnings: &mut WarningsBuilder) {
+        let starts = siblings
+            .iter()
+            .filter_map(|words| words.iter().find(|word| is_word(word.as_str())))
+            .filter_map(|word| Some((word, VerbForm::of(word)?)))
+            .collect::<Vec<_>>();
+        let Some((first, form)) = starts.first() else {
+            return;
+        };
+
+        for (word, _) in starts.iter().filter(|(_, other)| other != form) {
+            warnings.add_warning(
+                WarningBuilder::new(std::slice::from_ref(*word))
+                    .message(format!(
+                        "Sibling headings start with {}, such as \"{}\"",
+                        form.as_str(),
+                        first.as_str()
+                    ))
+                    .build(),
+            );
+        }
+    }
+}
+
+/// Does `word` have an uppercase letter after its first letter, e.g. "API" or
+/// "GitHub"? These are left as they are.
+fn has_inner_capitals(word: &str) -> bool {
+    word.chars().skip(1).any(char::is_uppercase)
+}
+
+/// Uppercase the first letter of `word`.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Group the headings in `doc` into siblings, which have the same level and
+/// the same parent heading.
+fn siblings<'a, 'input>(doc: &'a Document<'input>) -> Vec<Vec<&'a [Word<'input>]>> {
+    let mut groups: Vec<Vec<&[Word]>> = Vec::new();
+    // The index in `groups` of the open group at each level.
+    let mut open: Vec<Option<usize>> = Vec::new();
+
+    for block in doc.iter() {
+        let BlockKind::Heading(level) = block.kind() else {
+            continue;
+        };
+        let level = level as usize;
+        open.resize(open.len().max(level + 1), None);
+        open[level + 1..].fill(None);
+        let group = *open[level].get_or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[group].push(block.as_slice());
+    }
+
+    groups
+}
+
+impl Rule for HeadingStyle {
+    fn id(&self) -> &str {
+        "heading-style"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        if options.max_words == 0 {
+            return Err("max-words must be at least 1".into());
+        }
+        *self = HeadingStyle::new(options);
+        Ok(())
+    }
+
+    fn is_document_wide(&self) -> bool {
+        true
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let headings = doc
+            .iter()
+            .filter(|block| matches!(block.kind(), BlockKind::Heading(_)))
+            .map(Block::as_slice);
+        for words in headings {
+            self.check_case(words, doc.input(), warnings);
+            self.check_punctuation(words, warnings);
+            self.check_length(words, warnings);
+        }
+
+        for siblings in siblings(doc) {
+            self.check_siblings(&siblings, warnings);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::{Document, Edit},
+        parsers::MarkdownParser,
+        rule::{
+            test::{configured, markdown_fix_eq, markdown_messages_eq, markdown_spans_eq},
+            Rule, RuleSet,
+        },
+    };
+
+    use super::HeadingStyle;
+
+    #[test]
+    fn test_sentence_case() {
+        markdown_spans_eq(
+            HeadingStyle::default(),
+            "# Getting started with the API",
+            &[],
+        );
+        markdown_fix_eq(
+            HeadingStyle::default(),
+            "# getting Started With the API",
+            "# Getting started with the API",
+        );
+        markdown_messages_eq(
+            HeadingStyle::default(),
+            "# Getting Started",
+            &[(
+                "Started",
+                "\"Started\" should be \"started\" in sentence case",
+            )],
+        );
+
+        // Paragraphs aren't checked, and "I" and words after a colon may be
+        // capitalized.
+        markdown_spans_eq(HeadingStyle::default(), "Getting Started", &[]);
+        markdown_spans_eq(HeadingStyle::default(), "# Pastelito: A cat and I", &[]);
+
+        // A leading number isn't the first word.
+        markdown_spans_eq(HeadingStyle::default(), "# 1. Getting started", &[]);
+        markdown_fix_eq(
+            HeadingStyle::default(),
+            "# 2 getting Started",
+            "# 2 Getting started",
+        );
+    }
+
+    #[test]
+    fn test_title_case() {
+        let title = || configured::<HeadingStyle>(toml::toml! { case = "title" });
+        markdown_spans_eq(title(), "# Getting Started with the API", &[]);
+        markdown_fix_eq(
+            title(),
+            "# getting started With The API",
+            "# Getting Started with the API",
+        );
+        // The first and last words are always capitalized.
+        markdown_fix_eq(title(), "# the cat to sit on", "# The Cat to Sit On");
+    }
+
+    #[test]
+    fn test_exceptions() {
+        let rule = || {
+            configured::<HeadingStyle>(toml::toml! {
+                exceptions = ["Pastelito", "Visual Studio Code"]
+            })
+        };
+        markdown_spans_eq(rule(), "# Using Pastelito in Visual Studio Code", &[]);
+        markdown_fix_eq(
+            rule(),
+            "# Using pastelito in visual studio Code",
+            "# Using Pastelito in Visual Studio Code",
+        );
+        markdown_spans_eq(HeadingStyle::default(), "# Using Pastelito", &["Pastelito"]);
+    }
+
+    #[test]
+    fn test_punctuation() {
+        markdown_messages_eq(
+            HeadingStyle::default(),
+            "# Getting started.",
+            &[(".", "Headings should not end with \".\"")],
+        );
+        markdown_fix_eq(
+            HeadingStyle::default(),
+            "# Getting started:\n\n## Next steps!",
+            "# Getting started\n\n## Next steps",
+        );
+        markdown_spans_eq(HeadingStyle::default(), "# Why use it?", &[]);
+        markdown_spans_eq(HeadingStyle::default(), "It is good.", &[]);
+    }
+
+    #[test]
+    fn test_length() {
+        let input = "# One two three four five six seven eight nine ten eleven";
+        markdown_messages_eq(
+            HeadingStyle::default(),
+            input,
+            &[(
+                &input[2..],
+                "Heading has 11 words; keep headings to 10 words or fewer",
+            )],
+        );
+        markdown_spans_eq(
+            configured::<HeadingStyle>(toml::toml! { max-words = 11 }),
+            input,
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_siblings() {
+        let input = "# Cats\n\n\
+                     ## Describing the cat\n\n\
+                     ## Check the cat\n\n\
+                     ## Overview\n\n\
+                     ## Getting the cat\n\n\
+                     # Dogs\n\n\
+                     ## Check the dog\n\n\
+                     ### Getting the dog\n\n\
+                     ## Leave the dog";
+        markdown_messages_eq(
+            HeadingStyle::default(),
+            input,
+            &[(
+                "Check",
+                "Sibling headings start with a gerund, such as \"Describing\"",
+            )],
+        );
+    }
+
+    #[test]
+    fn test_incremental() {
+        let parser = MarkdownParser::default();
+        let ruleset = RuleSet::new(vec![Box::new(HeadingStyle::default())], Vec::new());
+        let input = "## Describing the cat\n\nText.\n\n## Getting the cat";
+        let edited = "## Check the cat\n\nText.\n\n## Getting the cat";
+
+        let doc = Document::new(&parser, input);
+        let results = ruleset.apply(&doc);
+        assert_eq!(results.iter_warnings().count(), 0);
+
+        // Editing the first heading adds a warning to the second heading.
+        let (updated, changes) = doc.update(&parser, Edit::between(input, edited), edited);
+        let results = ruleset.apply_incremental(&updated, &changes, &results);
+        assert_eq!(
+            results
+                .iter_warnings()
+                .map(|warning| warning.span.as_str(edited))
+                .collect::<Vec<_>>(),
+            vec!["Getting"]
+        );
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut rule = HeadingStyle::default();
+        assert_eq!(
+            rule.configure(&toml::toml! { max-words = 0 }).unwrap_err(),
+            "max-words must be at least 1"
+        );
+        assert!(rule
+            .configure(&toml::toml! { case = "upper" })
+            .unwrap_err()
+            .contains("unknown variant"));
+    }
+}
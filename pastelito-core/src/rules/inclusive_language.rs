@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use pastelito_model::Tag;
+use serde::Deserialize;
+
+use crate::{
+    phrases::{match_case, Phrases},
+    rule::{parse_options, Rule, RuleOptions, WarningBuilder, WarningsBuilder},
+    ByteSpan, Document, Word,
+};
+
+/// The bundled terms, mapping each term to its alternatives.
+static INCLUSIVE_LANGUAGE: &str = include_str!("data/inclusive_language.toml");
+
+/// The options for `InclusiveLanguage`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// More terms, added to the bundled terms.
+    terms: BTreeMap<String, Vec<String>>,
+}
+
+/// Flag non-inclusive terms, e.g. "whitelist", and suggest an inclusive
+/// alternative, e.g. "allowlist".
+///
+/// Proper nouns are never flagged, so "Master" in a product name is allowed.
+/// The terms come from a bundled list, which can be extended with the `terms`
+/// option.
+#[derive(Clone, Debug)]
+pub struct InclusiveLanguage {
+    /// Each term, and its alternatives. The first alternative is suggested as a
+    /// replacement.
+    terms: Phrases<Vec<String>>,
+}
+
+impl InclusiveLanguage {
+    /// Add `terms` to the list, replacing the alternatives for any existing
+    /// terms.
+    fn extend(&mut self, terms: BTreeMap<String, Vec<String>>) -> Result<(), String> {
+        for (term, alternatives) in terms {
+            if alternatives.is_empty() {
+                return Err(format!("no alternatives for {:?}", term));
+            }
+            if alternatives
+                .iter()
+                .any(|alternative| alternative.trim().is_empty())
+            {
+                return Err(format!("empty alternative for {:?}", term));
+            }
+            self.terms.insert(&term, alternatives);
+        }
+        Ok(())
+    }
+}
+
+impl Default for InclusiveLanguage {
+    fn default() -> Self {
+        let bundled: BTreeMap<String, Vec<String>> = toml::from_str(INCLUSIVE_LANGUAGE)
+            .expect("Internal error: invalid inclusive_language.toml");
+
+        let mut rule = InclusiveLanguage {
+            terms: Phrases::default(),
+        };
+        rule.extend(bundled)
+            .expect("Internal error: invalid inclusive_language.toml");
+        rule
+    }
+}
+
+/// Is `word` used as a verb in its base or present form, e.g. "master" in
+/// "you will master it" or "masters" in "he masters it"?
+///
+/// Taggers often tag these as nouns, so a word directly after a personal
+/// pronoun or a modal is also a verb. The past and "-ing" forms are still
+/// checked, as the bundled terms include verbs such as "blacklisted".
+fn is_verb(word: &Word, previous: Option<&Word>) -> bool {
+    let is_tagged_verb = matches!(
+        word.tag(),
+        Some(
+            Tag::VerbBaseForm
+                | Tag::VerbNon3rdPersonSingularPresent
+                | Tag::Verb3rdPersonSingularPresent
+        )
+    );
+    let follows_subject = previous
+        .is_some_and(|previous| matches!(previous.tag(), Some(Tag::PersonalPronoun | Tag::Modal)));
+    is_tagged_verb || follows_subject
+}
+
+/// Is `word` a possessive ending, e.g. "'s" in "master's"?
+fn is_possessive(word: &Word) -> bool {
+    word.tag() == Some(Tag::PossessiveEnding) || matches!(word.as_str(), "'s" | "'" | "’s" | "’")
+}
+
+impl Rule for InclusiveLanguage {
+    fn id(&self) -> &str {
+        "inclusive-language"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        self.extend(options.terms)
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        for block in doc.iter() {
+            let all_words = block.as_slice();
+            for (words, alternatives) in self.terms.find_all(all_words) {
+                if words.iter().any(Word::is_proper_noun) {
+                    continue;
+                }
+
+                let start = words[0].as_offset();
+                let end = words[words.len() - 1].as_span().end();
+                let previous = all_words.iter().rfind(|word| word.as_span().end() <= start);
+                let next = all_words.iter().find(|word| word.as_offset() >= end);
+
+                // "master" in "you will master it", but not "check" in "sanity
+                // check".
+                if let [word] = words {
+                    if is_verb(word, previous) {
+                        continue;
+                    }
+                }
+
+                // "a master's degree".
+                if next.is_some_and(is_possessive) {
+                    continue;
+                }
+
+                let span: ByteSpan = words.into();
+                let matched = span.as_str(doc.input());
+                let quoted = alternatives
+                    .iter()
+                    .map(|alternative| format!("\"{}\"", alternative))
+                    .collect::<Vec<_>>();
+                warnings.add_warning(
+                    WarningBuilder::new(words)
+                        .message(format!(
+                            "Prefer {} over \"{}\"",
+                            quoted.join(" or "),
+                            matched
+                        ))
+                        .suggestion(span, match_case(matched, &alternatives[0]))
+                        .build(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use pastelito_model::Tag;
+
+    use crate::{
+        doc::Document,
+        parsers::PlaintextParser,
+        rule::{
+            test::{fix_eq, spans_eq},
+            Rule, RuleSet,
+        },
+    };
+
+    use super::{InclusiveLanguage, INCLUSIVE_LANGUAGE};
+
+    #[test]
+    fn test() {
+        spans_eq(
+            InclusiveLanguage::default(),
+            "Add the host to the whitelist, not the blacklist.",
+            &["whitelist", "blacklist"],
+        );
+        spans_eq(
+            InclusiveLanguage::default(),
+            "It took ten man-hours to run a sanity check.",
+            &["man-hours", "sanity check"],
+        );
+        spans_eq(
+            InclusiveLanguage::default(),
+            "Thanks, you guys! Ask the chairman.",
+            &["you guys", "chairman"],
+        );
+        spans_eq(
+            InclusiveLanguage::default(),
+            "Add the host to the allowlist.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_fix() {
+        fix_eq(
+            InclusiveLanguage::default(),
+            "Only whitelisted hosts skip the sanity check.",
+            "Only allowlisted hosts skip the quick check.",
+        );
+        fix_eq(
+            InclusiveLanguage::default(),
+            "The slave reads from the master.",
+            "The replica reads from the primary.",
+        );
+    }
+
+    #[test]
+    fn test_verbs_and_possessives() {
+        for input in [
+            "He will master the technique.",
+            "He masters the skill.",
+            "She has a master's degree.",
+            "They both have masters' degrees.",
+        ] {
+            spans_eq(InclusiveLanguage::default(), input, &[]);
+        }
+        spans_eq(
+            InclusiveLanguage::default(),
+            "The host was blacklisted.",
+            &["blacklisted"],
+        );
+    }
+
+    #[test]
+    fn test_proper_noun() {
+        let input = "Use Master Lock on the master.";
+        let mut doc = Document::new(&PlaintextParser::default(), input);
+        for block in doc.iter_mut() {
+            for word in block.iter_mut() {
+                if word.as_str() == "Master" || word.as_str() == "Lock" {
+                    word.set_tag(Tag::ProperNounSingular);
+                } else if word.as_str() == "master" {
+                    word.set_tag(Tag::NounSingularOrMass);
+                }
+            }
+        }
+
+        let results =
+            RuleSet::new(vec![Box::new(InclusiveLanguage::default())], Vec::new()).apply(&doc);
+        let spans = results
+            .iter_warnings()
+            .map(|warning| warning.span.as_str(input))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec!["master"]);
+    }
+
+    /// Every bundled term matches itself.
+    #[test]
+    fn test_bundled() {
+        let bundled: BTreeMap<String, Vec<String>> = toml::from_str(INCLUSIVE_LANGUAGE).unwrap();
+        let rule = InclusiveLanguage::default();
+        for term in bundled.keys() {
+            spans_eq(rule.clone(), term, &[term]);
+        }
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut rule = InclusiveLanguage::default();
+        rule.configure(&toml::toml! {
+            [terms]
+            "guys" = ["everyone"]
+            "ninja" = ["expert"]
+        })
+        .unwrap();
+        fix_eq(
+            rule.clone(),
+            "Hi guys, meet our ninja.",
+            "Hi everyone, meet our expert.",
+        );
+
+        assert!(rule
+            .configure(&toml::toml! {
+                [terms]
+                "ninja" = []
+            })
+            .unwrap_err()
+            .contains("no alternatives"));
+        assert!(rule
+            .configure(&toml::toml! {
+                [terms]
+                "ninja" = ["expert", ""]
+            })
+            .unwrap_err()
+            .contains("empty alternative"));
+        assert!(rule
+            .configure(&toml::toml! { words = [] })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+}
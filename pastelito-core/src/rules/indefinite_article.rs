@@ -0,0 +1,276 @@
+use pastelito_model::Tag;
+
+use crate::{
+    matcher::{match_words, AndS, Any, IgnoreCase, Matcher, OneOfS},
+    phrases::match_case,
+    rule::{Rule, WarningBuilder, WarningsBuilder},
+    ByteSpan, Document, Word,
+};
+
+/// Prefixes of words which start with a vowel letter, but a consonant sound,
+/// e.g. "a user" or "a eulogy".
+///
+/// "one" is not a prefix, as "onerous" starts with a vowel sound. See
+/// `starts_with_one`.
+const CONSONANT_SOUND_PREFIXES: &[&str] = &[
+    "eu", "ewe", "once", "ouija", "ubiq", "ugand", "ukr", "unanim", "unic", "unif", "union",
+    "uniq", "unisex", "unison", "unit", "univ", "unix", "ura", "ure", "uri", "uro", "usa", "use",
+    "usu", "ute", "uti", "uto",
+];
+
+/// Prefixes of words which start with a consonant letter, but a vowel sound,
+/// e.g. "an hour".
+const VOWEL_SOUND_PREFIXES: &[&str] = &["heir", "honest", "honor", "honour", "hour"];
+
+/// Acronyms which are read as a word, rather than letter by letter, e.g. "a
+/// NASA mission".
+const WORD_ACRONYMS: &[&str] = &["fifa", "nasa", "nato", "unesco", "unicef"];
+
+/// Letters whose name starts with a vowel sound, e.g. "an MBA" or "an x-ray".
+const VOWEL_SOUND_LETTERS: &str = "aefhilmnorsx";
+
+/// Does `word` start with a vowel sound?
+///
+/// This is a heuristic, based on the spelling of the word. Returns `None` if
+/// the word doesn't start with a letter or a digit.
+fn starts_with_vowel_sound(word: &Word) -> Option<bool> {
+    let str = word.as_str();
+    let first = str.chars().next()?;
+
+    // Numbers may be written with digits, or as words, e.g. "an eight".
+    if word.tag() == Some(Tag::CardinalNumber) || first.is_ascii_digit() {
+        if let Some(is_vowel_sound) = number_starts_with_vowel_sound(str) {
+            return Some(is_vowel_sound);
+        }
+    }
+    if !first.is_alphabetic() {
+        return None;
+    }
+
+    let lowercase = str.to_lowercase();
+
+    // Acronyms, single letters and words such as "x-ray" are read letter by
+    // letter.
+    let is_acronym = str.chars().filter(|c| c.is_alphabetic()).count() >= 2
+        && str
+            .chars()
+            .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '-');
+    let is_letter = matches!(str.chars().nth(1), None | Some('-'));
+    if (is_acronym && !WORD_ACRONYMS.contains(&lowercase.as_str())) || is_letter {
+        return Some(VOWEL_SOUND_LETTERS.contains(first.to_ascii_lowercase()));
+    }
+
+    let has_prefix =
+        |prefixes: &[&str]| prefixes.iter().any(|prefix| lowercase.starts_with(prefix));
+    if starts_with_one(&lowercase) || has_prefix(CONSONANT_SOUND_PREFIXES) {
+        Some(false)
+    } else if has_prefix(VOWEL_SOUND_PREFIXES) {
+        Some(true)
+    } else {
+        Some(matches!(
+            lowercase.chars().next(),
+            Some('a' | 'e' | 'i' | 'o' | 'u')
+        ))
+    }
+}
+
+/// Does the lowercase `str` start with the word "one", e.g. "one", "one-time"
+/// or "one's", but not "onerous"?
+fn starts_with_one(str: &str) -> bool {
+    str.strip_prefix("one").is_some_and(|rest| {
+        rest.is_empty()
+            || rest.starts_with(|c: char| c == '-' || c == '\'' || c == '’' || c.is_ascii_digit())
+    })
+}
+
+/// Does the number at the start of `str` start with a vowel sound when read
+/// aloud, e.g. "an 8-bit" or "an 11th"?
+///
+/// Returns `None` if `str` doesn't start with a digit.
+fn number_starts_with_vowel_sound(str: &str) -> Option<bool> {
+    let digits = str
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == ',')
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+    if digits.is_empty() {
+        return None;
+    }
+
+    // "eight", "eighty", "eight hundred", and so on.
+    if digits.starts_with('8') {
+        return Some(true);
+    }
+
+    // "eleven" and "eighteen", including "eleven thousand" and so on, but not
+    // "one hundred and eleven".
+    Some(digits.len() % 3 == 2 && (digits.starts_with("11") || digits.starts_with("18")))
+}
+
+/// Check that "a" and "an" agree with the sound at the start of the next word,
+/// e.g. "an hour", "a user" or "an 8-bit".
+///
+/// `IndefiniteArticle` is not a `MatcherRule`, as it checks the text between
+/// the article and the next word. Markdown inline code is not a word, so "a
+/// `Document` object" must not be checked as "a object".
+pub struct IndefiniteArticle;
+
+impl IndefiniteArticle {
+    fn matcher() -> impl Matcher {
+        (
+            AndS(
+                Tag::Determiner,
+                OneOfS([IgnoreCase::new("a"), IgnoreCase::new("an")]),
+            ),
+            Any,
+        )
+    }
+}
+
+impl Rule for IndefiniteArticle {
+    fn id(&self) -> &str {
+        "indefinite-article"
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let matcher = Self::matcher();
+        for block in doc.iter() {
+            match_words(block, &matcher, |words| {
+                let [article, next] = words else {
+                    panic!("Internal error: expected 2 words in IndefiniteArticle");
+                };
+
+                // Skip the match if anything but whitespace, such as inline
+                // code, is between the words.
+                let gap = ByteSpan::new_unchecked(article.as_span().end(), next.as_offset());
+                if !gap.as_str(doc.input()).trim().is_empty() {
+                    return;
+                }
+
+                let Some(is_vowel_sound) = starts_with_vowel_sound(next) else {
+                    return;
+                };
+
+                let expected = if is_vowel_sound { "an" } else { "a" };
+                if article.as_str().eq_ignore_ascii_case(expected) {
+                    return;
+                }
+
+                warnings.add_warning(
+                    WarningBuilder::new(words)
+                        .message(format!("Use \"{}\" before \"{}\"", expected, next.as_str()))
+                        .suggestion(article.as_span(), match_case(article.as_str(), expected))
+                        .build(),
+                );
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::Document,
+        parsers::MarkdownParser,
+        rule::{
+            test::{fix_eq, spans_eq},
+            RuleSet,
+        },
+    };
+
+    use super::IndefiniteArticle;
+
+    /// Each input, and the text covered by each expected warning.
+    const CORPUS: &[(&str, &[&str])] = &[
+        // Vowels and consonants.
+        ("It is a cat.", &[]),
+        ("It is an apple.", &[]),
+        ("It is an cat.", &["an cat"]),
+        ("It is a apple.", &["a apple"]),
+        ("An cat sat.", &["An cat"]),
+        ("A apple fell.", &["A apple"]),
+        // Silent "h".
+        ("We waited an hour.", &[]),
+        ("We waited a hour.", &["a hour"]),
+        ("It was an honest mistake.", &[]),
+        ("It was a house.", &[]),
+        ("It was an house.", &["an house"]),
+        // Vowels with a consonant sound.
+        ("It has a user.", &[]),
+        ("It has an user.", &["an user"]),
+        ("It is a one-time fee.", &[]),
+        ("It is an one-time fee.", &["an one-time"]),
+        ("It is a one.", &[]),
+        ("It is an onerous task.", &[]),
+        ("It is a onerous task.", &["a onerous"]),
+        ("It is a unique item.", &[]),
+        ("It is a European city.", &[]),
+        ("It is an umbrella.", &[]),
+        ("It is an unusual item.", &[]),
+        ("It was an unissued share.", &[]),
+        ("It was a unissued share.", &["a unissued"]),
+        ("It is an unisolated case.", &[]),
+        ("They sang in unison, a unison chorus.", &[]),
+        ("It is a unisex shirt.", &[]),
+        // Acronyms.
+        ("She has an MBA.", &[]),
+        ("She has a MBA.", &["a MBA"]),
+        ("It is a URL.", &[]),
+        ("It is an URL.", &["an URL"]),
+        ("It is an HTTP request.", &[]),
+        ("It is a HTTP request.", &["a HTTP"]),
+        ("It is an API.", &[]),
+        ("It is a NASA mission.", &[]),
+        ("It is a GPU.", &[]),
+        // Letters and letter names.
+        ("It is an x-ray.", &[]),
+        ("It is a x-ray.", &["a x-ray"]),
+        ("It is a u-turn.", &[]),
+        ("It is an n-gram.", &[]),
+        // Numbers.
+        ("It is an 8-bit CPU.", &[]),
+        ("It is a 8-bit CPU.", &["a 8-bit"]),
+        ("It is a 16-bit CPU.", &[]),
+        ("It is an 11-year-old.", &[]),
+        ("It is an 18th birthday.", &[]),
+        ("It is a 100-year-old.", &[]),
+        ("It is a 110-year-old.", &[]),
+        ("It is an 80-year-old.", &[]),
+        ("It is an 800-page book.", &[]),
+        ("It is an 11,000-page book.", &[]),
+        ("It is a 1,100-page book.", &[]),
+        ("It is an eight.", &[]),
+        // Not followed by a word.
+        ("It is a \"big\" cat.", &[]),
+        ("It is a.", &[]),
+    ];
+
+    #[test]
+    fn test_corpus() {
+        for (input, expected) in CORPUS {
+            spans_eq(IndefiniteArticle, input, expected);
+        }
+    }
+
+    #[test]
+    fn test_inline_code() {
+        let doc = Document::new(
+            &MarkdownParser::default(),
+            "This is a `Document` object. A `Document` is a list of blocks.",
+        );
+        let results = RuleSet::new(vec![Box::new(IndefiniteArticle)], Vec::new()).apply(&doc);
+        assert_eq!(results.iter_warnings().count(), 0);
+    }
+
+    #[test]
+    fn test_fix() {
+        fix_eq(IndefiniteArticle, "We waited a hour.", "We waited an hour.");
+        fix_eq(IndefiniteArticle, "An user sat.", "A user sat.");
+        fix_eq(IndefiniteArticle, "A apple fell.", "An apple fell.");
+        fix_eq(
+            IndefiniteArticle,
+            "It is a onerous task.",
+            "It is an onerous task.",
+        );
+    }
+}
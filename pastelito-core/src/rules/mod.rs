@@ -1,17 +1,44 @@
 use crate::rule::Rule;
 
 mod academic_we;
+mod cliches;
+mod expletives;
+mod hard_sentences;
+mod heading_style;
+mod inclusive_language;
+mod indefinite_article;
 mod passive_construction;
 mod repeated_words;
+mod sentence_variety;
+mod spelling;
+mod spelling_variants;
+mod subject_verb_agreement;
+mod terminology;
+mod undefined_acronyms;
 mod weak_ing;
 mod weasel_words;
+mod wordy_phrases;
+
+pub(crate) use spelling::Spelling;
+pub(crate) use terminology::Terminology;
 
 pub(crate) fn default_rules() -> Vec<Box<dyn Rule>> {
     vec![
         Box::new(academic_we::AcademicWe),
+        Box::new(cliches::Cliches::default()),
+        Box::new(expletives::Expletives),
+        Box::new(hard_sentences::HardSentences::default()),
+        Box::new(heading_style::HeadingStyle::default()),
+        Box::new(inclusive_language::InclusiveLanguage::default()),
+        Box::new(indefinite_article::IndefiniteArticle),
         Box::new(passive_construction::PassiveConstruction),
-        Box::new(repeated_words::RepeatedWords),
+        Box::new(repeated_words::RepeatedWords::default()),
+        Box::new(sentence_variety::SentenceVariety::default()),
+        Box::new(spelling_variants::SpellingVariants::default()),
+        Box::new(subject_verb_agreement::SubjectVerbAgreement),
+        Box::new(undefined_acronyms::UndefinedAcronyms::default()),
         Box::new(weak_ing::WeakIng),
         Box::new(weasel_words::WeaselWords),
+        Box::new(wordy_phrases::WordyPhrases::default()),
     ]
 }
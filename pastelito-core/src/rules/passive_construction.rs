@@ -1,19 +1,101 @@
 use pastelito_model::Tag;
 
 use crate::{
-    matcher::{Matcher, Opt, Or},
+    matcher::{Ignore, IgnoreCase, Matcher, OneOfS, Opt, OrS, WithinSentence},
     rule::{MatcherRule, WarningBuilder, WarningsBuilder},
     Word,
 };
 
+/// Past participles which are usually adjectives describing a state, rather
+/// than a passive verb, e.g. "I am tired". These are only passive when they
+/// are followed by an agent, e.g. "I was surprised by the result".
+const ADJECTIVAL_PARTICIPLES: &[&str] = &[
+    "based",
+    "bored",
+    "concerned",
+    "confused",
+    "delighted",
+    "disappointed",
+    "engaged",
+    "excited",
+    "finished",
+    "gone",
+    "interested",
+    "involved",
+    "located",
+    "married",
+    "pleased",
+    "related",
+    "scared",
+    "supposed",
+    "surprised",
+    "tired",
+    "used",
+    "worried",
+];
+
+/// Linking verbs, which are never passive. After "be" or "get", these start a
+/// new clause, e.g. "the gift I got looked nice".
+const LINKING_VERBS: &[&str] = &[
+    "appeared", "became", "looked", "remained", "seemed", "smelled", "sounded", "tasted",
+];
+
 pub struct PassiveConstruction;
 
 impl MatcherRule for PassiveConstruction {
+    const ID: &'static str = "passive-construction";
+
+    /// A form of "be" or "get", followed by a past participle, and an optional
+    /// "by". Adverbs and negations between the words are ignored, e.g. "was
+    /// not quickly eaten".
     fn matcher() -> impl Matcher {
-        (Or("was", "were"), Opt(Tag::Adverb), Tag::VerbPastParticiple)
+        WithinSentence(Ignore(
+            OrS(Tag::Adverb, OneOfS(["not", "n't"])),
+            (
+                OneOfS([
+                    IgnoreCase::new("am"),
+                    IgnoreCase::new("is"),
+                    IgnoreCase::new("are"),
+                    IgnoreCase::new("was"),
+                    IgnoreCase::new("were"),
+                    IgnoreCase::new("be"),
+                    IgnoreCase::new("been"),
+                    IgnoreCase::new("being"),
+                    IgnoreCase::new("'m"),
+                    IgnoreCase::new("'re"),
+                    IgnoreCase::new("get"),
+                    IgnoreCase::new("gets"),
+                    IgnoreCase::new("got"),
+                    IgnoreCase::new("gotten"),
+                    IgnoreCase::new("getting"),
+                ]),
+                Tag::VerbPastParticiple,
+                Opt("by"),
+            ),
+        ))
     }
 
     fn on_match(words: &[Word], warnings: &mut WarningsBuilder) {
+        let (words, has_agent) = match words {
+            [verb, participle, _by] => (&[*verb, *participle][..], true),
+            _ => (words, false),
+        };
+
+        let participle = words[1].as_str();
+        if LINKING_VERBS
+            .iter()
+            .any(|verb| verb.eq_ignore_ascii_case(participle))
+        {
+            return;
+        }
+        if !has_agent
+            && ADJECTIVAL_PARTICIPLES
+                .iter()
+                .any(|adjective| adjective.eq_ignore_ascii_case(participle))
+        {
+            return;
+        }
+
         warnings.add_warning(
             WarningBuilder::new(words)
                 .message("Passive construction".into())
@@ -24,7 +106,7 @@ impl MatcherRule for PassiveConstruction {
 
 #[cfg(test)]
 mod tests {
-    use crate::rule::test::rule_eq;
+    use crate::rule::test::{rule_eq, spans_eq};
 
     use super::PassiveConstruction;
 
@@ -38,4 +120,87 @@ mod tests {
 
         rule_eq(PassiveConstruction, "They were asked to leave.", 1);
     }
+
+    /// Each input, and the text covered by each expected warning.
+    const CORPUS: &[(&str, &[&str])] = &[
+        // "was" and "were".
+        ("The item was broken.", &["was broken"]),
+        ("Mistakes were made.", &["were made"]),
+        ("They were asked to leave.", &["were asked"]),
+        ("The letter was written by hand.", &["was written"]),
+        // Other forms of "be".
+        ("I am told that it works.", &["am told"]),
+        ("The cake is eaten every day.", &["is eaten"]),
+        ("The results are shown below.", &["are shown"]),
+        ("It will be done soon.", &["be done"]),
+        ("The house has been sold.", &["been sold"]),
+        ("The road is being repaired.", &["being repaired"]),
+        ("We're told to wait.", &["'re told"]),
+        ("I'm told to wait.", &["'m told"]),
+        ("Was broken, it sat there.", &["Was broken"]),
+        // "get" passives.
+        ("The car got stolen.", &["got stolen"]),
+        ("He got fired.", &["got fired"]),
+        ("It gets broken often.", &["gets broken"]),
+        ("They will get caught.", &["get caught"]),
+        ("It has gotten broken.", &["gotten broken"]),
+        ("The server is getting restarted.", &["getting restarted"]),
+        // Negations.
+        ("The item was not broken.", &["was not broken"]),
+        ("Mistakes were not made.", &["were not made"]),
+        ("The window wasn't broken.", &["wasn't broken"]),
+        ("The results aren't shown.", &["aren't shown"]),
+        ("The bug is not fixed.", &["is not fixed"]),
+        ("He didn't get fired.", &["get fired"]),
+        // Adverbs.
+        ("The cake was quickly eaten.", &["was quickly eaten"]),
+        (
+            "The cake was very quickly eaten.",
+            &["was very quickly eaten"],
+        ),
+        ("The cake was not really eaten.", &["was not really eaten"]),
+        // Agents.
+        ("The letter was written by John.", &["was written"]),
+        ("She was surprised by the result.", &["was surprised"]),
+        // Several passives.
+        (
+            "The cake was eaten and the bed was made.",
+            &["was eaten", "was made"],
+        ),
+        ("It was broken. It got fixed.", &["was broken", "got fixed"]),
+        // Predicate adjectives.
+        ("I am tired.", &[]),
+        ("I am very tired.", &[]),
+        ("She is interested in art.", &[]),
+        ("He got married.", &[]),
+        ("The store is located downtown.", &[]),
+        ("You are supposed to go.", &[]),
+        ("They are used to it.", &[]),
+        ("He was gone.", &[]),
+        ("We were worried.", &[]),
+        // Not passive.
+        ("It was good.", &[]),
+        ("He was running.", &[]),
+        ("They were here.", &[]),
+        ("She has finished.", &[]),
+        ("There were mistakes.", &[]),
+        ("I was there when he arrived.", &[]),
+        ("It is a big cat.", &[]),
+        ("The cat sat on the mat.", &[]),
+        ("He gets the paper.", &[]),
+        ("We got a new dog.", &[]),
+        ("Is it big?", &[]),
+        // Past tense verbs after a clause ending in "be" or "get".
+        ("The gift I got looked nice.", &[]),
+        ("What it is seemed odd.", &[]),
+        // Passives don't cross sentences.
+        ("Where was it? Broken things lay there.", &[]),
+    ];
+
+    #[test]
+    fn test_corpus() {
+        for (input, expected) in CORPUS {
+            spans_eq(PassiveConstruction, input, expected);
+        }
+    }
 }
@@ -1,85 +1,211 @@
-use pastelito_model::Tag;
+use std::collections::HashSet;
+
+use serde::Deserialize;
 
 use crate::{
-    matcher::{Matcher, OneOf, Or},
-    rule::{MatcherRule, WarningBuilder, WarningsBuilder},
-    Word,
+    rule::{parse_options, Rule, RuleOptions, Severity, WarningBuilder, WarningsBuilder},
+    ByteSpan, Document, Word,
 };
 
-pub struct RepeatedWords;
-
-impl MatcherRule for RepeatedWords {
-    fn matcher() -> impl Matcher {
-        Or(
-            OneOf([
-                (Tag::Determiner, Tag::Determiner),
-                (
-                    Tag::VerbNon3rdPersonSingularPresent,
-                    Tag::VerbNon3rdPersonSingularPresent,
-                ),
-                (Tag::Modal, Tag::Modal),
-                (
-                    Tag::PrepositionOrSubordinatingConjunction,
-                    Tag::PrepositionOrSubordinatingConjunction,
-                ),
-                (Tag::PersonalPronoun, Tag::PersonalPronoun),
-                (Tag::PossesivePronoun, Tag::PossesivePronoun),
-                (Tag::To, Tag::To),
-            ]),
-            OneOf([("be", "be"), ("is", "is"), ("are", "are")]),
-        )
+/// Words which are often repeated on purpose, e.g. "he had had enough" or "he
+/// said that that was fine".
+///
+/// These are compared ignoring ASCII case.
+const ALLOWED: &[&str] = &["bye", "ha", "had", "no", "that"];
+
+/// The options for `RepeatedWords`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// More words which are allowed to repeat.
+    allow: Vec<String>,
+}
+
+/// Flag a word which is accidentally repeated, e.g. "the the".
+///
+/// The words are compared ignoring case, so repeats which start a sentence or
+/// are split by Markdown markup or a line break are found too. Words in the
+/// allow-list, such as "had had", are never flagged.
+#[derive(Clone, Debug)]
+pub struct RepeatedWords {
+    /// The lowercase words which are allowed to repeat.
+    allow: HashSet<String>,
+}
+
+impl Default for RepeatedWords {
+    fn default() -> Self {
+        RepeatedWords {
+            allow: ALLOWED.iter().map(|word| word.to_string()).collect(),
+        }
+    }
+}
+
+impl RepeatedWords {
+    /// Is `word` repeated by `next`?
+    fn is_repeated(&self, word: &Word, next: &Word) -> bool {
+        let str = word.as_str();
+        str.eq_ignore_ascii_case(next.as_str())
+            && str.chars().any(char::is_alphabetic)
+            && !self.allow.contains(&str.to_ascii_lowercase())
+    }
+}
+
+impl Rule for RepeatedWords {
+    fn id(&self) -> &str {
+        "repeated-words"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
     }
 
-    fn on_match(words: &[Word], warnings: &mut WarningsBuilder) {
-        assert_eq!(
-            words.len(),
-            2,
-            "Internal error: expected 2 words in RepeatedWords"
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        self.allow.extend(
+            options
+                .allow
+                .iter()
+                .map(|word| word.trim().to_ascii_lowercase()),
         );
+        Ok(())
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let input = doc.input();
+        for block in doc.iter() {
+            for words in block.as_slice().windows(2) {
+                let (word0, word1) = (words[0], words[1]);
+                if !self.is_repeated(&word0, &word1) {
+                    continue;
+                }
+
+                let mut warning = WarningBuilder::new(words).message("Repeated words".into());
 
-        let word0 = words[0];
-        let word1 = words[1];
+                // Only suggest deleting the second word if there is nothing but
+                // whitespace between the words. Otherwise, deleting it would
+                // also delete any markup.
+                let gap = ByteSpan::new_unchecked(word0.as_span().end(), word1.as_offset());
+                if gap.as_str(input).trim().is_empty() {
+                    warning = warning.suggestion(
+                        ByteSpan::new_unchecked(word0.as_span().end(), word1.as_span().end()),
+                        String::new(),
+                    );
+                }
 
-        if word0.as_str().eq_ignore_ascii_case(word1.as_str()) {
-            warnings.add_warning(
-                WarningBuilder::new(words)
-                    .message("Repeated words".into())
-                    .build(),
-            );
+                warnings.add_warning(warning.build());
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::rule::test::rule_eq;
+    use crate::rule::{
+        test::{fix_eq, markdown_fix_eq, markdown_spans_eq, rule_eq, spans_eq},
+        Rule,
+    };
 
     use super::RepeatedWords;
 
     #[test]
     fn test() {
         // Determiner
-        rule_eq(RepeatedWords, "An an apple.", 1);
+        rule_eq(RepeatedWords::default(), "An an apple.", 1);
         // VerbNon3rdPersonSingularPresent
-        rule_eq(RepeatedWords, "I am am describing.", 1);
+        rule_eq(RepeatedWords::default(), "I am am describing.", 1);
         // Modal
-        rule_eq(RepeatedWords, "I will will be describing.", 1);
+        rule_eq(RepeatedWords::default(), "I will will be describing.", 1);
         // PrepositionOrSubordinatingConjunction
-        rule_eq(RepeatedWords, "It is on on the table.", 1);
+        rule_eq(RepeatedWords::default(), "It is on on the table.", 1);
         // PersonalPronoun
-        rule_eq(RepeatedWords, "He he is here.", 1);
+        rule_eq(RepeatedWords::default(), "He he is here.", 1);
         // PossesivePronoun
-        rule_eq(RepeatedWords, "His his cat is here.", 1);
+        rule_eq(RepeatedWords::default(), "His his cat is here.", 1);
         // To
-        rule_eq(RepeatedWords, "We are going to to the store.", 1);
+        rule_eq(RepeatedWords::default(), "We are going to to the store.", 1);
         // be
-        rule_eq(RepeatedWords, "I will be be there.", 1);
+        rule_eq(RepeatedWords::default(), "I will be be there.", 1);
         // is
-        rule_eq(RepeatedWords, "It is is a cat.", 1);
+        rule_eq(RepeatedWords::default(), "It is is a cat.", 1);
         // are
-        rule_eq(RepeatedWords, "They are are here.", 1);
+        rule_eq(RepeatedWords::default(), "They are are here.", 1);
 
         // Prepositions are allowed to repeat if they are different
-        rule_eq(RepeatedWords, "Because because it was difficult.", 1);
+        rule_eq(
+            RepeatedWords::default(),
+            "Because because it was difficult.",
+            1,
+        );
+    }
+
+    #[test]
+    fn test_any_word() {
+        spans_eq(
+            RepeatedWords::default(),
+            "Check check the cat.",
+            &["Check check"],
+        );
+        spans_eq(
+            RepeatedWords::default(),
+            "The cat cat sat on the mat mat.",
+            &["cat cat", "mat mat"],
+        );
+        spans_eq(RepeatedWords::default(), "The cat sat.", &[]);
+        spans_eq(RepeatedWords::default(), "It was ... ... good.", &[]);
+        spans_eq(RepeatedWords::default(), "It scored 1 1.", &[]);
+    }
+
+    #[test]
+    fn test_allow() {
+        spans_eq(RepeatedWords::default(), "He had had enough.", &[]);
+        spans_eq(RepeatedWords::default(), "He said that that was fine.", &[]);
+        spans_eq(RepeatedWords::default(), "Bye bye.", &[]);
+
+        let mut rule = RepeatedWords::default();
+        rule.configure(&toml::toml! { allow = ["Very"] }).unwrap();
+        spans_eq(rule.clone(), "It was very very big.", &[]);
+        spans_eq(rule.clone(), "It was the the cat.", &["the the"]);
+
+        assert!(rule
+            .configure(&toml::toml! { words = [] })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+
+    #[test]
+    fn test_markdown() {
+        markdown_spans_eq(
+            RepeatedWords::default(),
+            "We sat on the\nthe mat.",
+            &["the\nthe"],
+        );
+        markdown_fix_eq(
+            RepeatedWords::default(),
+            "We sat on the\nthe mat.",
+            "We sat on the mat.",
+        );
+        // Deleting the second word would also delete the markup, so there is
+        // no suggestion.
+        markdown_spans_eq(
+            RepeatedWords::default(),
+            "The *the* cat sat.",
+            &["The *the"],
+        );
+        markdown_fix_eq(
+            RepeatedWords::default(),
+            "The *the* cat sat.",
+            "The *the* cat sat.",
+        );
+        markdown_spans_eq(RepeatedWords::default(), "# The cat\n\nThe cat sat.", &[]);
+    }
+
+    #[test]
+    fn test_fix() {
+        fix_eq(
+            RepeatedWords::default(),
+            "It is on on the table.",
+            "It is on the table.",
+        );
+        fix_eq(RepeatedWords::default(), "An an apple.", "An apple.");
     }
 }
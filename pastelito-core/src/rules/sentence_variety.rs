@@ -0,0 +1,347 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use pastelito_model::Tag;
+use serde::Deserialize;
+
+use crate::{
+    block::BlockKind,
+    readability::{is_word, TextCounts},
+    rule::{parse_options, Rule, RuleOptions, Severity, WarningBuilder, WarningsBuilder},
+    Document, Word,
+};
+
+/// The number of words whose tags make up the start pattern of a sentence,
+/// e.g. "DT NNS VBD" for "The cats jumped".
+const PATTERN_LEN: usize = 3;
+
+/// Flag monotonous sentences.
+///
+/// These are runs of consecutive sentences which start with the same word,
+/// e.g. "We... We... We...", or the same pattern of tags, e.g. "The cats
+/// jumped... Some dogs walked... These birds landed...". Paragraphs where most
+/// sentences have a similar length are flagged too.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SentenceVariety {
+    /// Runs of at least this many sentences which start the same way are
+    /// flagged.
+    min_run: usize,
+    /// The number of words in each length band, e.g. 5 for sentences with 1
+    /// to 4 words, 5 to 9 words, and so on.
+    band_width: usize,
+    /// The lengths are only checked in paragraphs with at least this many
+    /// sentences.
+    min_sentences: usize,
+}
+
+impl Default for SentenceVariety {
+    fn default() -> Self {
+        SentenceVariety {
+            min_run: 3,
+            band_width: 5,
+            min_sentences: 4,
+        }
+    }
+}
+
+/// Get the start of `sentence`, from its first word to its `len`th word,
+/// ignoring punctuation.
+///
+/// Returns `None` if the sentence has fewer than `len` words.
+fn start<'a, 'input>(sentence: &'a [Word<'input>], len: usize) -> Option<&'a [Word<'input>]> {
+    let mut indices = sentence
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| is_word(word.as_str()))
+        .map(|(i, _)| i);
+    let first = indices.next()?;
+    let last = if len > 1 {
+        indices.nth(len - 2)?
+    } else {
+        first
+    };
+    Some(&sentence[first..=last])
+}
+
+/// The lowercase first word of `sentence`.
+fn start_word(sentence: &[Word]) -> Option<String> {
+    start(sentence, 1).map(|words| words[0].as_str().to_lowercase())
+}
+
+/// The tags of the first `PATTERN_LEN` words of `sentence`.
+fn start_pattern(sentence: &[Word]) -> Option<Vec<Tag>> {
+    start(sentence, PATTERN_LEN)?
+        .iter()
+        .filter(|word| is_word(word.as_str()))
+        .map(Word::tag)
+        .collect()
+}
+
+/// Find the runs of at least `min_run` consecutive keys which are equal, and
+/// not `None`.
+fn runs<T: PartialEq>(keys: &[Option<T>], min_run: usize) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut run_start = 0;
+    for i in 1..=keys.len() {
+        let is_end = i == keys.len() || keys[i].is_none() || keys[i] != keys[run_start];
+        if !is_end {
+            continue;
+        }
+        if keys[run_start].is_some() && i - run_start >= min_run {
+            runs.push(run_start..i);
+        }
+        run_start = i;
+    }
+    runs
+}
+
+impl SentenceVariety {
+    /// Flag runs of sentences which start with the same word, or the same
+    /// pattern of tags.
+    ///
+    /// A sentence is only flagged once, so a run of sentences starting with
+    /// "The" isn't also flagged as a run of the same pattern.
+    fn check_starts(&self, sentences: &[&[Word]], warnings: &mut WarningsBuilder) {
+        let mut flagged = vec![false; sentences.len()];
+
+        let words = sentences
+            .iter()
+            .map(|sentence| start_word(sentence))
+            .collect::<Vec<_>>();
+        for run in runs(&words, self.min_run) {
+            for i in run.clone() {
+                flagged[i] = true;
+                let start = start(sentences[i], 1).unwrap();
+                warnings.add_warning(
+                    WarningBuilder::new(start)
+                        .message(format!(
+                            "{} consecutive sentences start with \"{}\"",
+                            run.len(),
+                            start[0].as_str()
+                        ))
+                        .build(),
+                );
+            }
+        }
+
+        let patterns = sentences
+            .iter()
+            .map(|sentence| start_pattern(sentence))
+            .collect::<Vec<_>>();
+        for run in runs(&patterns, self.min_run) {
+            for i in run.clone().filter(|i| !flagged[*i]) {
+                let start = start(sentences[i], PATTERN_LEN).unwrap();
+                warnings.add_warning(
+                    WarningBuilder::new(start)
+                        .message(format!(
+                            "{} consecutive sentences start with the same pattern",
+                            run.len()
+                        ))
+                        .build(),
+                );
+            }
+        }
+    }
+
+    /// Flag `paragraph` if most of its sentences are in the same length band.
+    fn check_lengths(
+        &self,
+        paragraph: &[Word],
+        sentences: &[&[Word]],
+        warnings: &mut WarningsBuilder,
+    ) {
+        let lengths = sentences
+            .iter()
+            .map(|sentence| TextCounts::of_sentence(sentence).words)
+            .filter(|&words| words > 0)
+            .collect::<Vec<_>>();
+        if lengths.len() < self.min_sentences {
+            return;
+        }
+
+        let mut bands = BTreeMap::<usize, usize>::new();
+        for length in &lengths {
+            *bands.entry(length / self.band_width).or_default() += 1;
+        }
+        let Some((band, count)) = bands.into_iter().max_by_key(|(_, count)| *count) else {
+            return;
+        };
+        if count * 2 <= lengths.len() {
+            return;
+        }
+
+        let min = (band * self.band_width).max(1);
+        let max = (band + 1) * self.band_width - 1;
+        warnings.add_warning(
+            WarningBuilder::new(paragraph)
+                .message(format!(
+                    "{} of {} sentences have {} to {} words",
+                    count,
+                    lengths.len(),
+                    min,
+                    max
+                ))
+                .build(),
+        );
+    }
+}
+
+impl Rule for SentenceVariety {
+    fn id(&self) -> &str {
+        "sentence-variety"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let configured: SentenceVariety = parse_options(options)?;
+        if configured.min_run < 2 {
+            return Err("min-run must be at least 2".into());
+        }
+        if configured.band_width == 0 {
+            return Err("band-width must be at least 1".into());
+        }
+        if configured.min_sentences < 2 {
+            return Err("min-sentences must be at least 2".into());
+        }
+        *self = configured;
+        Ok(())
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        for block in doc.iter() {
+            let sentences = block.sentences().collect::<Vec<_>>();
+            self.check_starts(&sentences, warnings);
+            if block.kind() == BlockKind::Paragraph {
+                self.check_lengths(block.as_slice(), &sentences, warnings);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use crate::rule::{
+        test::{configured, spans_eq},
+        Rule,
+    };
+
+    use super::{runs, SentenceVariety};
+
+    #[test]
+    fn test_runs() {
+        assert_eq!(runs(&[Some(1), Some(1), Some(1)], 3), vec![0..3]);
+        assert_eq!(
+            runs(&[Some(1), Some(1), Some(2)], 3),
+            Vec::<Range<usize>>::new()
+        );
+        assert_eq!(
+            runs(&[Some(2), Some(1), Some(1), Some(1), Some(1), None], 3),
+            vec![1..5]
+        );
+        assert_eq!(
+            runs::<usize>(&[None, None, None], 3),
+            Vec::<Range<usize>>::new()
+        );
+        assert_eq!(runs::<usize>(&[], 3), Vec::<Range<usize>>::new());
+    }
+
+    #[test]
+    fn test_start_word() {
+        spans_eq(
+            SentenceVariety::default(),
+            "The cat sat. The dog ran. The bird flew.",
+            &["The", "The", "The"],
+        );
+        spans_eq(
+            SentenceVariety::default(),
+            "We left early. \"We ate.\" we slept.",
+            &["We", "We", "we"],
+        );
+        spans_eq(
+            SentenceVariety::default(),
+            "The cat sat. The dog ran. A bird flew.",
+            &[],
+        );
+        spans_eq(
+            SentenceVariety::default(),
+            "The cat sat, the dog ran, and the bird flew.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_start_pattern() {
+        spans_eq(
+            SentenceVariety::default(),
+            "The cats jumped. Some dogs walked. These birds landed.",
+            &["The cats jumped", "Some dogs walked", "These birds landed"],
+        );
+        // These sentences are all short, so don't check their lengths.
+        spans_eq(
+            configured::<SentenceVariety>(toml::toml! { min-sentences = 10 }),
+            "The cats jumped. Some dogs walked. These birds landed. We left.",
+            &["The cats jumped", "Some dogs walked", "These birds landed"],
+        );
+        spans_eq(
+            SentenceVariety::default(),
+            "The cats jumped. Some dogs walked. We left.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_lengths() {
+        let input = "The cat sat on the mat. We left the house early. \
+                     It rained all day long. Then the sun came out.";
+        spans_eq(SentenceVariety::default(), input, &[input]);
+        spans_eq(
+            SentenceVariety::default(),
+            "The cat sat. We left the house very early in the morning. \
+             It rained. Then the sun came out and the birds sang all day.",
+            &[],
+        );
+        spans_eq(
+            SentenceVariety::default(),
+            "The cat sat on the mat. We left the house early. It rained.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_configure() {
+        let rule = configured::<SentenceVariety>(toml::toml! { min-run = 2 });
+        spans_eq(rule, "The cat sat. The dog ran.", &["The", "The"]);
+
+        let input = "The cat sat. We left the house very early in the morning. \
+                     It rained. Then the sun came out and the birds sang all day.";
+        let rule = configured::<SentenceVariety>(toml::toml! { band-width = 20 });
+        spans_eq(rule, input, &[input]);
+
+        let input = "The cat sat on the mat. We left the house early. It rained all day.";
+        let rule = configured::<SentenceVariety>(toml::toml! { min-sentences = 3 });
+        spans_eq(rule, input, &[input]);
+
+        let mut rule = SentenceVariety::default();
+        assert!(rule
+            .configure(&toml::toml! { min-run = 1 })
+            .unwrap_err()
+            .contains("min-run"));
+        assert!(rule
+            .configure(&toml::toml! { band-width = 0 })
+            .unwrap_err()
+            .contains("band-width"));
+        assert!(rule
+            .configure(&toml::toml! { min-sentences = 1 })
+            .unwrap_err()
+            .contains("min-sentences"));
+        assert!(rule
+            .configure(&toml::toml! { words = [] })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+}
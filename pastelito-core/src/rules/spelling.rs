@@ -0,0 +1,333 @@
+use pastelito_model::Tag;
+use serde::Deserialize;
+
+use crate::{
+    dictionary::Dictionary,
+    rule::{parse_options, Rule, RuleOptions, WarningBuilder, WarningsBuilder},
+    tokenize::CONTRACTIONS,
+    ByteSpan, Document, Word,
+};
+
+/// The default number of suggestions for each misspelled word.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The options for `Spelling`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct Options {
+    /// More words which are spelled correctly, e.g. project names.
+    words: Vec<String>,
+    /// The maximum number of suggestions for each misspelled word.
+    max_suggestions: Option<usize>,
+}
+
+/// Flag words which are not in any of the spelling dictionaries, with
+/// suggested corrections.
+///
+/// The dictionaries and word lists are set by `dictionaries` and `word-lists`
+/// in `pastelito.toml`, so this rule is created by `Config::build_ruleset`.
+/// Without a dictionary, no words are flagged.
+///
+/// Numbers, URLs, email addresses, inline code and file names are not
+/// checked. Contractions split off by the tokenizer, such as "n't" and "'s",
+/// are checked together with the word before them.
+pub struct Spelling {
+    /// The Hunspell dictionaries. A word is correct if any of these allow it.
+    dictionaries: Vec<Dictionary>,
+    /// More correct words, from the word lists and the `words` option.
+    word_lists: Vec<Dictionary>,
+    max_suggestions: usize,
+}
+
+impl Spelling {
+    pub fn new(dictionaries: Vec<Dictionary>, word_lists: Vec<Dictionary>) -> Self {
+        Spelling {
+            dictionaries,
+            word_lists,
+            max_suggestions: MAX_SUGGESTIONS,
+        }
+    }
+
+    /// Is `word` in any of the dictionaries or word lists?
+    fn is_known(&self, word: &str) -> bool {
+        self.dictionaries
+            .iter()
+            .chain(&self.word_lists)
+            .any(|dictionary| dictionary.check(word))
+    }
+
+    /// Is `word` spelled correctly?
+    ///
+    /// Words with a curly apostrophe are checked with a straight apostrophe.
+    /// Contractions which the tokenizer doesn't split, such as "it’s", and
+    /// compound words, such as "cat-like" or "and/or", are allowed if each
+    /// part is spelled correctly.
+    fn check(&self, word: &str) -> bool {
+        let word = word.replace('\u{2019}', "'");
+        if self.is_known(&word) {
+            return true;
+        }
+
+        let is_contraction = CONTRACTIONS.iter().any(|contraction| {
+            word.len() > contraction.len()
+                && word.is_char_boundary(word.len() - contraction.len())
+                && word[word.len() - contraction.len()..].eq_ignore_ascii_case(contraction)
+                && self.is_known(&word[..word.len() - contraction.len()])
+        });
+        if is_contraction {
+            return true;
+        }
+
+        word.contains(['-', '/'])
+            && word
+                .split(['-', '/'])
+                .all(|part| part.is_empty() || self.is_known(part))
+    }
+
+    /// Suggest corrections for `word`, most likely first.
+    fn suggest(&self, word: &str) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        for dictionary in self.dictionaries.iter().chain(&self.word_lists) {
+            for suggestion in dictionary.suggest(word, self.max_suggestions) {
+                if !suggestions.contains(&suggestion) {
+                    suggestions.push(suggestion);
+                }
+            }
+        }
+        suggestions.truncate(self.max_suggestions);
+        suggestions
+    }
+}
+
+/// Is `word` a contraction split off by the tokenizer, e.g. "n't" in "isn't"?
+fn is_contraction(word: &Word) -> bool {
+    CONTRACTIONS
+        .iter()
+        .any(|contraction| contraction.eq_ignore_ascii_case(word.as_str()))
+}
+
+/// Should `word` be skipped, as it is a number, a URL, inline code, or
+/// something else which isn't a dictionary word?
+fn is_skipped(word: &Word) -> bool {
+    let str = word.as_str();
+    word.tag() == Some(Tag::CardinalNumber)
+        || !str.contains(char::is_alphabetic)
+        || str.contains(|c: char| c.is_ascii_digit())
+        // URLs and email addresses.
+        || str.contains("://")
+        || str.starts_with("www.")
+        || str.contains('@')
+        // Inline code in plain text.
+        || str.contains('`')
+        // Abbreviations, e.g. "e.g", and file names, e.g. "config.toml".
+        || str.contains('.')
+}
+
+impl Rule for Spelling {
+    fn id(&self) -> &str {
+        "spelling"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        if !options.words.is_empty() {
+            let mut words = Dictionary::default();
+            for word in &options.words {
+                words.add_word(word.trim());
+            }
+            self.word_lists.push(words);
+        }
+        if let Some(max_suggestions) = options.max_suggestions {
+            self.max_suggestions = max_suggestions;
+        }
+        Ok(())
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        if self.dictionaries.is_empty() {
+            return;
+        }
+
+        for block in doc.iter() {
+            let words = block.as_slice();
+            for (i, word) in words.iter().enumerate() {
+                if is_contraction(word) || is_skipped(word) {
+                    continue;
+                }
+
+                // Check a word split from a contraction together with the
+                // contraction, e.g. "can't" rather than "ca".
+                let contraction = words.get(i + 1).filter(|next| {
+                    is_contraction(next) && next.as_offset() == word.as_span().end()
+                });
+                if let Some(next) = contraction {
+                    let span = ByteSpan::new_unchecked(word.as_offset(), next.as_span().end());
+                    if self.check(span.as_str(doc.input())) {
+                        continue;
+                    }
+                }
+
+                if self.check(word.as_str()) {
+                    continue;
+                }
+
+                let mut warning = WarningBuilder::new(std::slice::from_ref(word))
+                    .message(format!("Unknown word \"{}\"", word.as_str()));
+                for suggestion in self.suggest(word.as_str()) {
+                    warning = warning.suggestion(word.as_span(), suggestion);
+                }
+                warnings.add_warning(warning.build());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dictionary::Dictionary,
+        doc::Document,
+        parsers::{MarkdownParser, PlaintextParser},
+        rule::{
+            test::{fix_eq, spans_eq},
+            Rule, RuleSet,
+        },
+    };
+
+    use super::Spelling;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+
+SFX S Y 2
+SFX S 0 es [sxzh]
+SFX S 0 s [^sxzh]
+";
+
+    const DIC: &str = "\
+17
+a
+at
+box/S
+ca
+can
+can't
+cat/S
+here
+in
+is
+it
+like
+or
+sat
+the
+they
+with
+";
+
+    fn spelling() -> Spelling {
+        Spelling::new(vec![Dictionary::new(AFF, DIC).unwrap()], Vec::new())
+    }
+
+    /// The suggestions for each warning.
+    fn suggestions(rule: Spelling, input: &str) -> Vec<Vec<String>> {
+        let doc = Document::new(&PlaintextParser::default(), input);
+        let results = RuleSet::new(vec![Box::new(rule)], Vec::new()).apply(&doc);
+        results
+            .iter_warnings()
+            .map(|warning| {
+                warning
+                    .suggestions
+                    .iter()
+                    .map(|suggestion| suggestion.replacement.clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test() {
+        spans_eq(spelling(), "The cat sat.", &[]);
+        spans_eq(spelling(), "Teh cat szt.", &["Teh", "szt"]);
+        spans_eq(spelling(), "THE CATS SAT HERE.", &[]);
+        spans_eq(spelling(), "The cat sat with boxes.", &[]);
+        spans_eq(spelling(), "The cat sat with boxs.", &["boxs"]);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        fix_eq(spelling(), "Teh cat sat.", "The cat sat.");
+        assert_eq!(
+            suggestions(spelling(), "The cta sat."),
+            vec![vec!["cat", "ca"]]
+        );
+        assert_eq!(
+            suggestions(spelling(), "The zzzz sat."),
+            vec![Vec::<String>::new()]
+        );
+
+        let mut rule = spelling();
+        rule.configure(&toml::toml! { max-suggestions = 1 })
+            .unwrap();
+        assert_eq!(suggestions(rule, "The cta sat."), vec![vec!["cat"]]);
+    }
+
+    #[test]
+    fn test_contractions() {
+        spans_eq(spelling(), "It's the cat's box. It isn't here.", &[]);
+        spans_eq(spelling(), "They can't. They're here.", &[]);
+        spans_eq(spelling(), "It\u{2019}s the cat\u{2019}s box.", &[]);
+        spans_eq(spelling(), "The cat won't.", &["wo"]);
+    }
+
+    #[test]
+    fn test_compounds() {
+        spans_eq(spelling(), "It is cat-like, a cat/box.", &[]);
+        spans_eq(spelling(), "It is cat-lyke.", &["cat-lyke"]);
+    }
+
+    #[test]
+    fn test_skipped() {
+        spans_eq(spelling(), "The 3 cats sat at 10:30.", &[]);
+        spans_eq(spelling(), "The cat sat at https://example.com/catz.", &[]);
+        spans_eq(
+            spelling(),
+            "The cat sat at www.catz.com or catz@example.com.",
+            &[],
+        );
+        spans_eq(
+            spelling(),
+            "The `catz` sat with 2nd cat in config.toml.",
+            &[],
+        );
+
+        let input = "The `catz` sat at <https://example.com/catz>.";
+        let doc = Document::new(&MarkdownParser::default(), input);
+        let results = RuleSet::new(vec![Box::new(spelling())], Vec::new()).apply(&doc);
+        assert_eq!(results.iter_warnings().count(), 0);
+    }
+
+    #[test]
+    fn test_words() {
+        let mut rule = spelling();
+        rule.configure(&toml::toml! { words = ["pastelito"] })
+            .unwrap();
+        spans_eq(rule, "The cat is a pastelito. Pastelito sat.", &[]);
+
+        let mut rule = spelling();
+        assert!(rule
+            .configure(&toml::toml! { dictionary = "en_US" })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+
+    #[test]
+    fn test_no_dictionary() {
+        spans_eq(Spelling::new(Vec::new(), Vec::new()), "Teh cat szt.", &[]);
+
+        let mut words = Dictionary::default();
+        words.add_word("cat");
+        spans_eq(Spelling::new(Vec::new(), vec![words]), "Teh cat szt.", &[]);
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{
+    phrases::match_case,
+    rule::{parse_options, Rule, RuleOptions, WarningBuilder, WarningsBuilder},
+    Document, Word,
+};
+
+/// The bundled words, with their spelling in each locale.
+static SPELLING_VARIANTS: &str = include_str!("data/spelling_variants.toml");
+
+/// The spelling of a word in each locale, in the order of `LOCALES`.
+type Row = [String; 4];
+
+/// A variety of English, or `Auto` to use the variety which is most common in
+/// the document.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+enum Locale {
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "en-US")]
+    EnUs,
+    #[serde(rename = "en-GB")]
+    EnGb,
+    #[serde(rename = "en-AU")]
+    EnAu,
+    #[serde(rename = "en-CA")]
+    EnCa,
+}
+
+/// The locales in each `Row`.
+const LOCALES: [Locale; 4] = [Locale::EnUs, Locale::EnGb, Locale::EnAu, Locale::EnCa];
+
+impl Locale {
+    /// The index of the locale in each `Row`, or `None` for `Auto`.
+    fn column(self) -> Option<usize> {
+        LOCALES.iter().position(|locale| *locale == self)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Locale::Auto => "auto",
+            Locale::EnUs => "en-US",
+            Locale::EnGb => "en-GB",
+            Locale::EnAu => "en-AU",
+            Locale::EnCa => "en-CA",
+        }
+    }
+}
+
+/// The options for `SpellingVariants`. The bundled `spelling_variants.toml`
+/// has the same format, with only `words`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    locale: Option<Locale>,
+    words: Vec<Row>,
+}
+
+/// A word in the document which is spelled differently in some locales.
+struct Found<'a, 'input> {
+    word: &'a Word<'input>,
+    row: &'a Row,
+}
+
+impl Found<'_, '_> {
+    /// Is the word spelled correctly for the locale in `column`?
+    fn matches(&self, column: usize) -> bool {
+        self.row[column].eq_ignore_ascii_case(self.word.as_str())
+    }
+}
+
+/// Flag words spelled for a different variety of English, e.g. "colour" in an
+/// en-US document, and suggest the spelling for the document's locale.
+///
+/// The `locale` option is one of `en-US`, `en-GB`, `en-AU` or `en-CA`. By
+/// default it is `auto`, which finds the locale that matches the most words in
+/// the document and flags the rest, so a document is only flagged if it mixes
+/// spellings. Proper nouns are never flagged. The words come from a bundled
+/// table, which can be extended with the `words` option.
+pub struct SpellingVariants {
+    locale: Locale,
+    words: Vec<Row>,
+    /// The index in `words` of each lowercase spelling.
+    spellings: HashMap<String, usize>,
+}
+
+impl SpellingVariants {
+    /// Add more words, replacing the row for any existing spelling.
+    fn extend(&mut self, words: Vec<Row>) -> Result<(), String> {
+        for row in words {
+            if row.iter().any(|spelling| spelling.trim().is_empty()) {
+                return Err(format!("empty spelling in {:?}", row));
+            }
+            for spelling in &row {
+                self.spellings
+                    .insert(spelling.to_lowercase(), self.words.len());
+            }
+            self.words.push(row);
+        }
+        Ok(())
+    }
+
+    /// Find the words in `doc` which are spelled differently in some locales.
+    fn find<'a, 'input>(&'a self, doc: &'a Document<'input>) -> Vec<Found<'a, 'input>> {
+        doc.iter()
+            .flat_map(|block| block.as_slice())
+            .filter(|word| !word.is_proper_noun())
+            .filter_map(|word| {
+                let row = &self.words[*self.spellings.get(&word.as_str().to_lowercase())?];
+                Some(Found { word, row })
+            })
+            .collect()
+    }
+
+    /// Find the locale whose spellings match the most words.
+    ///
+    /// Returns `None` if no locale has more matches than the others, e.g. if
+    /// the document uses "color" and "colour" once each.
+    fn dominant(found: &[Found]) -> Option<usize> {
+        let mismatches = (0..LOCALES.len())
+            .map(|column| {
+                (0..found.len())
+                    .filter(|i| !found[*i].matches(column))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let fewest = mismatches.iter().map(Vec::len).min()?;
+        let mut best = (0..LOCALES.len()).filter(|column| mismatches[*column].len() == fewest);
+
+        // Locales which would flag the same words, e.g. en-GB and en-AU, are
+        // equally good.
+        let column = best.next()?;
+        best.all(|other| mismatches[other] == mismatches[column])
+            .then_some(column)
+    }
+}
+
+impl Default for SpellingVariants {
+    fn default() -> Self {
+        let bundled: Options = toml::from_str(SPELLING_VARIANTS)
+            .expect("Internal error: invalid spelling_variants.toml");
+
+        let mut rule = SpellingVariants {
+            locale: Locale::default(),
+            words: Vec::new(),
+            spellings: HashMap::new(),
+        };
+        rule.extend(bundled.words)
+            .expect("Internal error: invalid spelling_variants.toml");
+        rule
+    }
+}
+
+impl Rule for SpellingVariants {
+    fn id(&self) -> &str {
+        "spelling-variants"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        if let Some(locale) = options.locale {
+            self.locale = locale;
+        }
+        self.extend(options.words)
+    }
+
+    /// In `auto` mode, a word is flagged depending on the spellings in the
+    /// rest of the document.
+    fn is_document_wide(&self) -> bool {
+        self.locale == Locale::Auto
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let found = self.find(doc);
+        let Some(column) = self.locale.column().or_else(|| Self::dominant(&found)) else {
+            return;
+        };
+
+        for found in found.iter().filter(|found| !found.matches(column)) {
+            let matched = found.word.as_str();
+            let expected = &found.row[column];
+            let message = match self.locale {
+                Locale::Auto => format!(
+                    "\"{}\" is inconsistent with the rest of the document; use \"{}\"",
+                    matched, expected
+                ),
+                locale => format!(
+                    "\"{}\" is not the {} spelling; use \"{}\"",
+                    matched,
+                    locale.as_str(),
+                    expected
+                ),
+            };
+            warnings.add_warning(
+                WarningBuilder::new(std::slice::from_ref(found.word))
+                    .message(message)
+                    .suggestion(found.word.as_span(), match_case(matched, expected))
+                    .build(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::{Document, Edit},
+        parsers::PlaintextParser,
+        rule::{
+            test::{configured, fix_eq, messages_eq, spans_eq},
+            Rule, RuleSet,
+        },
+    };
+
+    use super::{Options, SpellingVariants, LOCALES, SPELLING_VARIANTS};
+
+    fn locale(locale: &str) -> SpellingVariants {
+        configured(toml::toml! { locale = locale })
+    }
+
+    #[test]
+    fn test_bundled() {
+        let bundled: Options = toml::from_str(SPELLING_VARIANTS).unwrap();
+        assert!(bundled.locale.is_none());
+        for row in &bundled.words {
+            assert!(
+                row.iter().any(|spelling| *spelling != row[0]),
+                "{:?} is the same in every locale",
+                row
+            );
+        }
+        assert_eq!(LOCALES.len(), bundled.words[0].len());
+    }
+
+    #[test]
+    fn test_locale() {
+        let input = "The colour of the catalog is gray, and we organise it.";
+        spans_eq(locale("en-US"), input, &["colour", "organise"]);
+        spans_eq(locale("en-GB"), input, &["catalog", "gray"]);
+        spans_eq(locale("en-AU"), input, &["catalog", "gray"]);
+        spans_eq(locale("en-CA"), input, &["catalog", "gray", "organise"]);
+
+        fix_eq(
+            locale("en-GB"),
+            input,
+            "The colour of the catalogue is grey, and we organise it.",
+        );
+        fix_eq(
+            locale("en-CA"),
+            "We analyse the colors.",
+            "We analyze the colours.",
+        );
+    }
+
+    #[test]
+    fn test_auto() {
+        // Consistent documents aren't flagged.
+        spans_eq(
+            SpellingVariants::default(),
+            "The color of the catalog.",
+            &[],
+        );
+        spans_eq(
+            SpellingVariants::default(),
+            "The colour of the catalogue. We organize it.",
+            &[],
+        );
+
+        // The minority spellings are flagged, across blocks.
+        spans_eq(
+            SpellingVariants::default(),
+            "The color of the catalog.\n\nWe analyze the colour.",
+            &["colour"],
+        );
+        fix_eq(
+            SpellingVariants::default(),
+            "The colour of the catalogue is gray.",
+            "The colour of the catalogue is grey.",
+        );
+
+        // Without a dominant locale, nothing is flagged.
+        spans_eq(SpellingVariants::default(), "The color of the colour.", &[]);
+        spans_eq(
+            SpellingVariants::default(),
+            "The colours. We organize and organise it.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_messages() {
+        messages_eq(
+            locale("en-GB"),
+            "The color.",
+            &[(
+                "color",
+                "\"color\" is not the en-GB spelling; use \"colour\"",
+            )],
+        );
+        messages_eq(
+            SpellingVariants::default(),
+            "The color of the colours and centres.",
+            &[(
+                "color",
+                "\"color\" is inconsistent with the rest of the document; use \"colour\"",
+            )],
+        );
+    }
+
+    #[test]
+    fn test_incremental() {
+        let parser = PlaintextParser::default();
+        let ruleset = RuleSet::new(vec![Box::new(SpellingVariants::default())], Vec::new());
+        let input = "The color.\n\nThe colour.\n\nThe gray cat.";
+        let edited = "The color.\n\nThe colour.\n\nThe grey cat.";
+
+        let doc = Document::new(&parser, input);
+        let results = ruleset.apply(&doc);
+        assert_eq!(results.iter_warnings().count(), 1);
+
+        // Editing the last block changes the dominant locale.
+        let (updated, changes) = doc.update(&parser, Edit::between(input, edited), edited);
+        let results = ruleset.apply_incremental(&updated, &changes, &results);
+        assert_eq!(
+            results
+                .iter_warnings()
+                .map(|warning| warning.span.as_str(edited))
+                .collect::<Vec<_>>(),
+            vec!["color"]
+        );
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut rule = SpellingVariants::default();
+        rule.configure(&toml::toml! {
+            locale = "en-US"
+            words = [["jail", "gaol", "gaol", "jail"]]
+        })
+        .unwrap();
+        fix_eq(rule, "The gaol.", "The jail.");
+
+        let mut rule = SpellingVariants::default();
+        assert!(rule
+            .configure(&toml::toml! { locale = "en-NZ" })
+            .unwrap_err()
+            .contains("unknown variant"));
+        assert!(rule
+            .configure(&toml::toml! { words = [["color", "colour", "", "colour"]] })
+            .unwrap_err()
+            .contains("empty spelling"));
+    }
+}
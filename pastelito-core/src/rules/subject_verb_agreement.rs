@@ -0,0 +1,324 @@
+use pastelito_model::Tag;
+
+use crate::{
+    matcher::{
+        match_words, AndS, Ignore, IgnoreCase, Matcher, OneOfS, Or, OrS, StrFn, WithinSentence,
+    },
+    rule::{Rule, WarningBuilder, WarningsBuilder},
+    Document, Word,
+};
+
+/// Verbs which are followed by an object and a bare infinitive, e.g. "let it
+/// run" or "make the servers run".
+const CAUSATIVE_VERBS: &[&str] = &[
+    "bid", "feel", "feels", "felt", "had", "has", "have", "having", "hear", "heard", "hears",
+    "help", "helped", "helping", "helps", "let", "lets", "letting", "made", "make", "makes",
+    "making", "saw", "see", "seeing", "sees", "watch", "watched", "watches", "watching",
+];
+
+/// Words which start a question, where the subject is followed by a bare
+/// infinitive, e.g. "does the server run?".
+const QUESTION_VERBS: &[&str] = &["did", "do", "does"];
+
+/// Prepositions which are followed by a noun phrase that is not the subject,
+/// e.g. "the list of servers runs".
+const PREPOSITIONS: &[&str] = &[
+    "about", "above", "across", "after", "against", "among", "around", "at", "before", "behind",
+    "below", "beneath", "beside", "between", "beyond", "by", "during", "except", "for", "from",
+    "in", "inside", "into", "like", "near", "of", "on", "onto", "outside", "over", "per", "since",
+    "through", "to", "toward", "towards", "under", "until", "upon", "via", "with", "within",
+    "without",
+];
+
+/// Plural nouns which are often used with a singular verb, e.g. "the data is".
+const SINGULAR_PLURALS: &[&str] = &[
+    "analytics",
+    "data",
+    "economics",
+    "headquarters",
+    "mathematics",
+    "means",
+    "media",
+    "metadata",
+    "news",
+    "physics",
+    "politics",
+    "series",
+    "species",
+    "statistics",
+];
+
+/// Flag simple subject-verb agreement errors, where a singular subject is
+/// followed by a plural verb, e.g. "the server run", or a plural subject is
+/// followed by a singular verb, e.g. "the servers runs".
+///
+/// `SubjectVerbAgreement` is not a `MatcherRule`, as it checks the words before
+/// the subject to avoid false positives, e.g. "let it run" or "the list of
+/// servers runs".
+pub struct SubjectVerbAgreement;
+
+impl SubjectVerbAgreement {
+    /// A subject directly followed by a verb which doesn't agree with it.
+    /// Adverbs between the subject and verb are ignored, e.g. "the server
+    /// quickly run".
+    fn matcher() -> impl Matcher {
+        WithinSentence(Ignore(
+            Tag::Adverb,
+            Or(
+                (
+                    OrS(
+                        Tag::NounSingularOrMass,
+                        AndS(
+                            Tag::PersonalPronoun,
+                            OneOfS([
+                                IgnoreCase::new("he"),
+                                IgnoreCase::new("she"),
+                                IgnoreCase::new("it"),
+                            ]),
+                        ),
+                    ),
+                    Tag::VerbNon3rdPersonSingularPresent,
+                ),
+                (
+                    OrS(
+                        Tag::NounPlural,
+                        AndS(
+                            Tag::PersonalPronoun,
+                            OneOfS([
+                                IgnoreCase::new("i"),
+                                IgnoreCase::new("we"),
+                                IgnoreCase::new("you"),
+                                IgnoreCase::new("they"),
+                            ]),
+                        ),
+                    ),
+                    // "'s" is often a possessive, e.g. "the children's".
+                    AndS(Tag::Verb3rdPersonSingularPresent, StrFn(|str| str != "'s")),
+                ),
+            ),
+        ))
+    }
+}
+
+/// Is `word` part of a noun phrase, before its last noun?
+fn is_noun_phrase_word(word: &Word) -> bool {
+    matches!(
+        word.tag(),
+        Some(
+            Tag::Determiner
+                | Tag::Predeterminer
+                | Tag::Adjective
+                | Tag::AdjectiveComparative
+                | Tag::AdjectiveSuperlative
+                | Tag::Adverb
+                | Tag::CardinalNumber
+                | Tag::NounSingularOrMass
+                | Tag::NounPlural
+                | Tag::ProperNounSingular
+                | Tag::ProperNounPlural
+                | Tag::PossesivePronoun
+                | Tag::PossessiveEnding
+        )
+    )
+}
+
+/// Is `word` a number, e.g. "10" in "10 dollars is enough"?
+fn is_number(word: &Word) -> bool {
+    word.tag() == Some(Tag::CardinalNumber)
+        || word.as_str().starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn is_one_of(word: &Word, words: &[&str]) -> bool {
+    words
+        .iter()
+        .any(|other| other.eq_ignore_ascii_case(word.as_str()))
+}
+
+/// Does `word` look like a plural noun, e.g. "warnings", but not "class",
+/// "status" or "analysis"?
+fn looks_plural(word: &Word) -> bool {
+    let str = word.as_str().to_ascii_lowercase();
+    str.ends_with('s') && !str.ends_with("ss") && !str.ends_with("us") && !str.ends_with("is")
+}
+
+/// Is the subject at `words[subject]`, followed by `verb`, likely to be a false
+/// positive?
+///
+/// This checks the noun phrase containing the subject, and the word before the
+/// noun phrase.
+fn is_false_positive(words: &[Word], subject: usize, verb: &Word) -> bool {
+    let word = &words[subject];
+    if !word.as_str().contains(char::is_alphabetic) || is_one_of(word, SINGULAR_PLURALS) {
+        return true;
+    }
+
+    // Taggers often tag plurals as singular nouns, e.g. "the warnings are".
+    if word.tag() == Some(Tag::NounSingularOrMass) && looks_plural(word) {
+        return true;
+    }
+
+    // A pronoun is a whole noun phrase, but a noun may have a determiner and
+    // adjectives before it.
+    let is_pronoun = words[subject].tag() == Some(Tag::PersonalPronoun);
+    let mut start = subject;
+    // Some prepositions are tagged as adverbs, e.g. "below".
+    while !is_pronoun
+        && start > 0
+        && is_noun_phrase_word(&words[start - 1])
+        && !is_one_of(&words[start - 1], PREPOSITIONS)
+    {
+        start -= 1;
+    }
+
+    // A quantity is usually singular, e.g. "10 dollars is enough".
+    if words[start..subject].iter().any(is_number) {
+        return true;
+    }
+
+    let Some(previous) = start.checked_sub(1).map(|i| &words[i]) else {
+        return false;
+    };
+
+    // A singular noun may be the last of several subjects, e.g. "the client
+    // and the server run".
+    let is_compound = matches!(
+        previous.tag(),
+        Some(Tag::CoordinatingConjunction | Tag::Comma)
+    );
+    if is_compound && verb.tag() == Some(Tag::VerbNon3rdPersonSingularPresent) {
+        return true;
+    }
+
+    previous.tag() == Some(Tag::Modal)
+        || is_one_of(previous, CAUSATIVE_VERBS)
+        || is_one_of(previous, QUESTION_VERBS)
+        || is_one_of(previous, PREPOSITIONS)
+}
+
+impl Rule for SubjectVerbAgreement {
+    fn id(&self) -> &str {
+        "subject-verb-agreement"
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let matcher = Self::matcher();
+        for block in doc.iter() {
+            let words = block.as_slice();
+            match_words(block, &matcher, |matched| {
+                let (subject, verb) = (matched[0], matched[1]);
+                let index = words.partition_point(|word| word.as_offset() < subject.as_offset());
+                if is_false_positive(words, index, &verb) {
+                    return;
+                }
+
+                warnings.add_warning(
+                    WarningBuilder::new(matched)
+                        .message(format!(
+                            "\"{}\" does not agree with \"{}\"",
+                            verb.as_str(),
+                            subject.as_str()
+                        ))
+                        .build(),
+                );
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::test::spans_eq;
+
+    use super::SubjectVerbAgreement;
+
+    #[test]
+    fn test() {
+        spans_eq(SubjectVerbAgreement, "The server run.", &["server run"]);
+        spans_eq(SubjectVerbAgreement, "The servers runs.", &["servers runs"]);
+        spans_eq(SubjectVerbAgreement, "He run every day.", &["He run"]);
+        spans_eq(SubjectVerbAgreement, "It have a cat.", &["It have"]);
+        spans_eq(SubjectVerbAgreement, "They runs.", &["They runs"]);
+        spans_eq(SubjectVerbAgreement, "We has a cat.", &["We has"]);
+        spans_eq(
+            SubjectVerbAgreement,
+            "Mistakes is common.",
+            &["Mistakes is"],
+        );
+        spans_eq(
+            SubjectVerbAgreement,
+            "The big green cat do it.",
+            &["cat do"],
+        );
+        spans_eq(
+            SubjectVerbAgreement,
+            "The client and the servers runs.",
+            &["servers runs"],
+        );
+    }
+
+    #[test]
+    fn test_adverbs() {
+        spans_eq(
+            SubjectVerbAgreement,
+            "The server quickly run.",
+            &["server quickly run"],
+        );
+        spans_eq(
+            SubjectVerbAgreement,
+            "The servers often really runs.",
+            &["servers often really runs"],
+        );
+    }
+
+    /// Sentences which are correct, and must not be flagged.
+    const FALSE_POSITIVES: &[&str] = &[
+        "The server runs.",
+        "The servers run.",
+        "He runs every day.",
+        "They run.",
+        "I run.",
+        "You have a cat.",
+        "It has a cat.",
+        "The server quickly runs.",
+        // Bare infinitives.
+        "Let it run.",
+        "We made the server run.",
+        "Please help the servers run.",
+        "Does the server run?",
+        "Why does it run?",
+        "Can it run?",
+        "The server will run.",
+        // Prepositional phrases.
+        "The list of servers runs daily.",
+        "One of the servers runs daily.",
+        "The cost of the big servers is high.",
+        // Singular plurals and quantities.
+        "The data is ready.",
+        "The news is good.",
+        "10 dollars is enough.",
+        // Subjects and verbs in different sentences.
+        "It was the server. Run it.",
+        "Where are the servers? Is it here?",
+        // Other words between the subject and verb.
+        "The servers, which run daily, are here.",
+        "The servers that run are here.",
+        // Plurals which are tagged as singular nouns.
+        "The warnings are displayed here.",
+        "Config files are read from the directory.",
+        "Most sentences have a grade.",
+        "The measurements are shown in the panel.",
+        // Punctuation tagged as a noun.
+        "Use and / are both fine.",
+        // Compound subjects.
+        "The client and the server run.",
+        "A title, a paragraph, a list item are all blocks.",
+        "Warnings below the threshold are dropped.",
+    ];
+
+    #[test]
+    fn test_false_positives() {
+        for input in FALSE_POSITIVES {
+            spans_eq(SubjectVerbAgreement, input, &[]);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::{
+    glossary::{Glossary, Term},
+    phrases::{match_case, Phrases},
+    rule::{parse_options, Rule, RuleOptions, WarningBuilder, WarningsBuilder},
+    ByteSpan, Document, Word,
+};
+
+/// The options for `Terminology`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// More terms, added to the terms from the glossaries.
+    terms: Vec<Term>,
+}
+
+/// A spelling of a term, which the phrases map to.
+#[derive(Clone, Debug)]
+struct Spelling {
+    /// The index of the term in `Terminology::terms`.
+    term: usize,
+    /// Is this one of the term's variants, rather than the term itself?
+    is_variant: bool,
+}
+
+/// Flag terms which don't match the project glossary, e.g. "Github" or
+/// "e-mail", and suggest the preferred spelling, e.g. "GitHub" or "email".
+///
+/// The glossaries are set by `glossaries` in `pastelito.toml`, so this rule is
+/// created by `Config::build_ruleset`. More terms can be added with the
+/// `terms` option. See the `glossary` module for the format.
+pub struct Terminology {
+    terms: Vec<Term>,
+    /// Each spelling of each term and its variants, ignoring case.
+    spellings: Phrases<Vec<Spelling>>,
+}
+
+impl Terminology {
+    pub fn new(glossaries: Vec<Glossary>) -> Self {
+        let mut rule = Terminology {
+            terms: Vec::new(),
+            spellings: Phrases::default(),
+        };
+        rule.extend(glossaries.into_iter().flat_map(|glossary| glossary.terms));
+        rule
+    }
+
+    /// Add more terms.
+    ///
+    /// Several terms can share a spelling, e.g. "sign in" is a verb, and a
+    /// variant of the noun "sign-in", so the phrases are rebuilt from all of
+    /// the terms.
+    fn extend(&mut self, terms: impl IntoIterator<Item = Term>) {
+        self.terms.extend(terms);
+
+        let mut spellings = BTreeMap::<String, Vec<Spelling>>::new();
+        for (i, term) in self.terms.iter().enumerate() {
+            let variants = term.variants.iter().map(|variant| (variant, true));
+            for (phrase, is_variant) in [(&term.term, false)].into_iter().chain(variants) {
+                let key = phrase
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_lowercase();
+                spellings.entry(key).or_default().push(Spelling {
+                    term: i,
+                    is_variant,
+                });
+            }
+        }
+
+        self.spellings = Phrases::default();
+        for (phrase, spellings) in spellings {
+            self.spellings.insert(&phrase, spellings);
+        }
+    }
+}
+
+/// Is `words` used as the part of speech of `term`, if it has one?
+fn matches_pos(term: &Term, words: &[Word]) -> bool {
+    term.pos.is_none_or(|pos| {
+        words
+            .iter()
+            .any(|word| word.tag().is_some_and(|tag| pos.matches(tag)))
+    })
+}
+
+/// Is `text` the same as `term`, including its case?
+///
+/// A term starting with a lowercase letter may also be capitalized, e.g.
+/// "Email" at the start of a sentence.
+fn matches_case(text: &str, term: &str) -> bool {
+    text == term || (term.starts_with(char::is_lowercase) && text == match_case(text, term))
+}
+
+impl Rule for Terminology {
+    fn id(&self) -> &str {
+        "terminology"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        for (index, term) in options.terms.iter().enumerate() {
+            term.validate()
+                .map_err(|message| format!("term {}: {}", index, message))?;
+        }
+        self.extend(options.terms);
+        Ok(())
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        for block in doc.iter() {
+            for (words, spellings) in self.spellings.find_all(block.as_slice()) {
+                let span: ByteSpan = words.into();
+                let matched = span.as_str(doc.input());
+                let text = matched.split_whitespace().collect::<Vec<_>>().join(" ");
+
+                let spellings = spellings
+                    .iter()
+                    .map(|spelling| (spelling, &self.terms[spelling.term]))
+                    .filter(|(_, term)| matches_pos(term, words))
+                    .collect::<Vec<_>>();
+                let is_correct = spellings.iter().any(|(spelling, term)| {
+                    !spelling.is_variant && matches_case(&text, &term.term)
+                });
+                if is_correct {
+                    continue;
+                }
+
+                // Prefer a term which only differs in case, over a term which
+                // has this as a variant.
+                let Some((_, term)) = spellings
+                    .iter()
+                    .find(|(spelling, _)| !spelling.is_variant)
+                    .or(spellings.first())
+                else {
+                    continue;
+                };
+
+                let message = match term.pos {
+                    Some(pos) => format!(
+                        "Use \"{}\" rather than \"{}\" as {} {}",
+                        term.term,
+                        text,
+                        if pos.as_str().starts_with('a') {
+                            "an"
+                        } else {
+                            "a"
+                        },
+                        pos.as_str()
+                    ),
+                    None => format!("Use \"{}\" rather than \"{}\"", term.term, text),
+                };
+                let replacement = if term.term.starts_with(char::is_lowercase) {
+                    match_case(matched, &term.term)
+                } else {
+                    term.term.clone()
+                };
+                warnings.add_warning(
+                    WarningBuilder::new(words)
+                        .message(message)
+                        .suggestion(span, replacement)
+                        .build(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        glossary::Glossary,
+        rule::{
+            test::{fix_eq, messages_eq, spans_eq},
+            Rule,
+        },
+    };
+
+    use super::Terminology;
+
+    const GLOSSARY: &str = r#"
+        [[terms]]
+        term = "GitHub"
+
+        [[terms]]
+        term = "email"
+        variants = ["e-mail"]
+
+        [[terms]]
+        term = "sign in"
+        pos = "verb"
+        variants = ["sign-in", "signin"]
+
+        [[terms]]
+        term = "sign-in"
+        pos = "noun"
+        variants = ["sign in", "signin"]
+    "#;
+
+    fn terminology() -> Terminology {
+        Terminology::new(vec![Glossary::from_toml_str(GLOSSARY).unwrap()])
+    }
+
+    #[test]
+    fn test_case() {
+        spans_eq(
+            terminology(),
+            "We use Github and github, but GitHub is fine.",
+            &["Github", "github"],
+        );
+        fix_eq(
+            terminology(),
+            "We use Github and GITHUB.",
+            "We use GitHub and GitHub.",
+        );
+        spans_eq(terminology(), "Email us, or send an email.", &[]);
+        spans_eq(terminology(), "Send an EMAIL.", &["EMAIL"]);
+    }
+
+    #[test]
+    fn test_variants() {
+        spans_eq(
+            terminology(),
+            "Send an e-mail. E-mail us.",
+            &["e-mail", "E-mail"],
+        );
+        fix_eq(
+            terminology(),
+            "Send an e-mail. E-mail us.",
+            "Send an email. Email us.",
+        );
+        messages_eq(
+            terminology(),
+            "Send an e-mail.",
+            &[("e-mail", "Use \"email\" rather than \"e-mail\"")],
+        );
+    }
+
+    #[test]
+    fn test_pos() {
+        spans_eq(terminology(), "You must sign in.", &[]);
+        spans_eq(terminology(), "The sign-in is slow.", &[]);
+        fix_eq(terminology(), "You must sign-in.", "You must sign in.");
+        fix_eq(
+            terminology(),
+            "The sign in is slow.",
+            "The sign-in is slow.",
+        );
+        messages_eq(
+            terminology(),
+            "The sign in is slow.",
+            &[(
+                "sign in",
+                "Use \"sign-in\" rather than \"sign in\" as a noun",
+            )],
+        );
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut rule = terminology();
+        rule.configure(&toml::toml! {
+            [[terms]]
+            term = "JavaScript"
+            variants = ["JS"]
+        })
+        .unwrap();
+        fix_eq(
+            rule,
+            "We use Javascript and JS on GitHub.",
+            "We use JavaScript and JavaScript on GitHub.",
+        );
+
+        let mut rule = terminology();
+        assert_eq!(
+            rule.configure(&toml::toml! {
+                [[terms]]
+                term = ""
+            })
+            .unwrap_err(),
+            "term 0: empty term"
+        );
+        assert!(rule
+            .configure(&toml::toml! { glossary = "glossary.toml" })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+
+    #[test]
+    fn test_empty() {
+        spans_eq(Terminology::new(Vec::new()), "We use Github.", &[]);
+    }
+}
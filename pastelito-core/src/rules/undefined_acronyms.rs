@@ -0,0 +1,413 @@
+use std::collections::HashSet;
+
+use pastelito_model::Tag;
+use serde::Deserialize;
+
+use crate::{
+    block::BlockKind,
+    readability::is_word,
+    rule::{parse_options, Rule, RuleOptions, WarningBuilder, WarningsBuilder},
+    Document, Word,
+};
+
+/// Acronyms which are understood without being defined.
+const ALLOWED: &[&str] = &[
+    "AI", "AM", "API", "ASCII", "CPU", "CSS", "CSV", "EU", "FAQ", "GPU", "HTML", "HTTP", "HTTPS",
+    "ID", "IP", "JSON", "OK", "OS", "PDF", "PM", "RAM", "SQL", "TODO", "TV", "UI", "UK", "URL",
+    "US", "USA", "USB", "UTF", "XML",
+];
+
+/// The maximum number of words in the long form of an acronym, in
+/// parentheses after the acronym.
+const MAX_PARENTHESIZED_WORDS: usize = 10;
+
+/// The options for `UndefinedAcronyms`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// More acronyms which don't need to be defined.
+    allow: Vec<String>,
+}
+
+/// A use of an acronym in the document.
+struct Use<'a, 'input> {
+    word: &'a Word<'input>,
+    /// The acronym, without a plural "s".
+    acronym: &'input str,
+    /// Does this use define the acronym?
+    is_definition: bool,
+}
+
+/// Flag acronyms which aren't defined on their first use, e.g. "LSP" rather
+/// than "Language Server Protocol (LSP)".
+///
+/// An acronym is an all-caps word tagged as a proper noun. It is defined by a
+/// long form followed by the acronym in parentheses, or the acronym followed
+/// by the long form in parentheses, e.g. "LSP (Language Server Protocol)".
+/// The letters of the acronym must appear in order in the long form, starting
+/// with the first letter of one of its words.
+///
+/// This compares the whole document, so an acronym is only flagged at its first
+/// use. Uses in headings are ignored, as acronyms are usually defined in the
+/// text after a heading. Acronyms in the allow-list, such as "HTML" and "URL",
+/// are never flagged.
+#[derive(Clone, Debug)]
+pub struct UndefinedAcronyms {
+    /// The uppercase acronyms which don't need to be defined.
+    allow: HashSet<String>,
+}
+
+impl Default for UndefinedAcronyms {
+    fn default() -> Self {
+        UndefinedAcronyms {
+            allow: ALLOWED.iter().map(|acronym| acronym.to_string()).collect(),
+        }
+    }
+}
+
+/// Get the acronym in `word`, e.g. "LSP" for "LSP" or "LSPs".
+///
+/// Returns `None` if `word` is not an all-caps proper noun with at least two
+/// letters, or is a Roman numeral, e.g. "II".
+fn acronym<'input>(word: &Word<'input>) -> Option<&'input str> {
+    let str = word.as_str();
+    let (str, is_plural) = match str.strip_suffix('s') {
+        Some(singular) => (singular, true),
+        None => (str, false),
+    };
+
+    // Taggers often tag plural acronyms as plural nouns.
+    let is_noun = match word.tag()? {
+        Tag::ProperNounSingular | Tag::ProperNounPlural => true,
+        Tag::NounPlural => is_plural,
+        _ => false,
+    };
+    let is_acronym = str.starts_with(|c: char| c.is_ascii_uppercase())
+        && str.chars().filter(char::is_ascii_uppercase).count() >= 2
+        && str
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+    (is_noun && is_acronym && !is_roman_numeral(str)).then_some(str)
+}
+
+/// Is `str` a Roman numeral, e.g. "II" in "World War II"?
+///
+/// The numeral must be well-formed, and either less than 100 or a year such as
+/// "MCMXC", so acronyms such as "CLI", "CD" and "MD" are not numerals.
+fn is_roman_numeral(str: &str) -> bool {
+    const NUMERALS: [(&str, usize); 13] = [
+        ("M", 1000),
+        ("CM", 900),
+        ("D", 500),
+        ("CD", 400),
+        ("C", 100),
+        ("XC", 90),
+        ("L", 50),
+        ("XL", 40),
+        ("X", 10),
+        ("IX", 9),
+        ("V", 5),
+        ("IV", 4),
+        ("I", 1),
+    ];
+
+    // Parse greedily, then check that `str` is how the value is written.
+    let mut rest = str;
+    let mut value = 0;
+    let mut written = String::new();
+    for (numeral, n) in NUMERALS {
+        while let Some(next) = rest.strip_prefix(numeral) {
+            rest = next;
+            value += n;
+        }
+    }
+    let mut remaining = value;
+    for (numeral, n) in NUMERALS {
+        while remaining >= n {
+            written.push_str(numeral);
+            remaining -= n;
+        }
+    }
+
+    rest.is_empty() && written == str && (value < 100 || (value >= 1000 && str.len() >= 4))
+}
+
+/// Is `long` a long form of `acronym`?
+///
+/// The letters and digits of the acronym must appear in `long`, in order, and
+/// the first must start a word. This matches "Language Server Protocol" and
+/// "JavaScript Object Notation" for "LSP" and "JSON".
+fn is_long_form(acronym: &str, long: &[&str]) -> bool {
+    let long = long.join(" ").to_lowercase().chars().collect::<Vec<_>>();
+    let short = acronym.to_lowercase().chars().collect::<Vec<_>>();
+
+    // Match from the end of both, so the long form is as short as possible.
+    let mut end = long.len();
+    for (i, c) in short.iter().enumerate().rev() {
+        loop {
+            let Some(j) = end.checked_sub(1) else {
+                return false;
+            };
+            end = j;
+            let starts_word = j == 0 || !long[j - 1].is_alphanumeric();
+            if long[j] == *c && (i > 0 || starts_word) {
+                break;
+            }
+        }
+    }
+    true
+}
+
+/// The text of each word in `words`.
+fn text<'input>(words: &[Word<'input>]) -> Vec<&'input str> {
+    words.iter().map(Word::as_str).collect()
+}
+
+/// Does the acronym at `words[i]` define the acronym?
+fn is_definition(words: &[Word], i: usize, acronym: &str) -> bool {
+    // "Long Form (ACRONYM)", where the long form is the words before the
+    // parenthesis, back to the last punctuation.
+    let is_parenthesized =
+        i > 0 && words[i - 1].as_str() == "(" && words.get(i + 1).map(Word::as_str) == Some(")");
+    if is_parenthesized {
+        let max_len = (acronym.len() + 5).min(acronym.len() * 2);
+        let before = &words[..i - 1];
+        let start = before
+            .iter()
+            .rposition(|word| !is_word(word.as_str()))
+            .map_or(0, |j| j + 1)
+            .max(before.len().saturating_sub(max_len));
+        if is_long_form(acronym, &text(&before[start..])) {
+            return true;
+        }
+    }
+
+    // "ACRONYM (Long Form)".
+    if words.get(i + 1).map(Word::as_str) == Some("(") {
+        let after = &words[i + 2..];
+        let end = after
+            .iter()
+            .take(MAX_PARENTHESIZED_WORDS + 1)
+            .position(|word| word.as_str() == ")");
+        if let Some(end) = end {
+            return end > 0 && is_long_form(acronym, &text(&after[..end]));
+        }
+    }
+
+    false
+}
+
+impl UndefinedAcronyms {
+    /// Find each use of an acronym in `doc`, in order, skipping uses in
+    /// headings which don't define the acronym.
+    fn find<'a, 'input>(&self, doc: &'a Document<'input>) -> Vec<Use<'a, 'input>> {
+        let mut uses = Vec::new();
+        for block in doc.iter() {
+            let is_heading = matches!(block.kind(), BlockKind::Heading(_));
+            let words = block.as_slice();
+            for (i, word) in words.iter().enumerate() {
+                let Some(acronym) = acronym(word) else {
+                    continue;
+                };
+                if self.allow.contains(acronym) {
+                    continue;
+                }
+                let is_definition = is_definition(words, i, acronym);
+                if is_heading && !is_definition {
+                    continue;
+                }
+                uses.push(Use {
+                    word,
+                    acronym,
+                    is_definition,
+                });
+            }
+        }
+        uses
+    }
+}
+
+impl Rule for UndefinedAcronyms {
+    fn id(&self) -> &str {
+        "undefined-acronyms"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        self.allow.extend(
+            options
+                .allow
+                .iter()
+                .map(|acronym| acronym.trim().to_ascii_uppercase()),
+        );
+        Ok(())
+    }
+
+    fn is_document_wide(&self) -> bool {
+        true
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let uses = self.find(doc);
+        let defined = uses
+            .iter()
+            .filter(|use_| use_.is_definition)
+            .map(|use_| use_.acronym)
+            .collect::<HashSet<_>>();
+
+        let mut seen = HashSet::new();
+        for use_ in &uses {
+            if !seen.insert(use_.acronym) || use_.is_definition {
+                continue;
+            }
+
+            let message = if defined.contains(use_.acronym) {
+                format!("\"{}\" is used before it is defined", use_.acronym)
+            } else {
+                format!(
+                    "\"{}\" is not defined; spell it out on first use, followed by \"({})\"",
+                    use_.acronym, use_.acronym
+                )
+            };
+            warnings.add_warning(
+                WarningBuilder::new(std::slice::from_ref(use_.word))
+                    .message(message)
+                    .build(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::{Document, Edit},
+        parsers::PlaintextParser,
+        rule::{
+            test::{markdown_messages_eq, spans_eq},
+            Rule, RuleSet,
+        },
+    };
+
+    use super::{is_long_form, is_roman_numeral, UndefinedAcronyms};
+
+    #[test]
+    fn test_long_form() {
+        assert!(is_long_form("LSP", &["Language", "Server", "Protocol"]));
+        assert!(is_long_form(
+            "LSP",
+            &["the", "Language", "Server", "Protocol"]
+        ));
+        assert!(is_long_form("JSON", &["JavaScript", "Object", "Notation"]));
+        assert!(is_long_form("MP3", &["MPEG", "Audio", "Layer", "3"]));
+        assert!(!is_long_form("LSP", &["Server", "Protocol"]));
+        assert!(!is_long_form("LSP", &["Protocol", "Server", "Language"]));
+        assert!(!is_long_form("LSP", &["pLSP"]));
+    }
+
+    #[test]
+    fn test_roman_numeral() {
+        for numeral in ["II", "IV", "VIII", "XIV", "XL", "XCIX", "MCMXC", "MMXXIV"] {
+            assert!(is_roman_numeral(numeral), "{:?}", numeral);
+        }
+        for acronym in ["CLI", "DVD", "MIDI", "DLL", "MD", "CD", "IIII", "VX", "IC"] {
+            assert!(!is_roman_numeral(acronym), "{:?}", acronym);
+        }
+    }
+
+    #[test]
+    fn test_defined() {
+        spans_eq(
+            UndefinedAcronyms::default(),
+            "The Language Server Protocol (LSP) is fast. The LSP is good.",
+            &[],
+        );
+        spans_eq(
+            UndefinedAcronyms::default(),
+            "LSP (Language Server Protocol) is fast. The LSP is good.",
+            &[],
+        );
+        spans_eq(
+            UndefinedAcronyms::default(),
+            "The Language Server Protocol (LSP) is fast.\n\nThe LSP is good.",
+            &[],
+        );
+    }
+
+    #[test]
+    fn test_undefined() {
+        spans_eq(
+            UndefinedAcronyms::default(),
+            "We use the LSP. The LSP is good.",
+            &["LSP"],
+        );
+        spans_eq(
+            UndefinedAcronyms::default(),
+            "The server (LSP) is fast. The LSP is good.",
+            &["LSP"],
+        );
+        spans_eq(UndefinedAcronyms::default(), "We ship two SDKs.", &["SDKs"]);
+        spans_eq(UndefinedAcronyms::default(), "We use the CLI.", &["CLI"]);
+        markdown_messages_eq(
+            UndefinedAcronyms::default(),
+            "We use the LSP.",
+            &[(
+                "LSP",
+                "\"LSP\" is not defined; spell it out on first use, followed by \"(LSP)\"",
+            )],
+        );
+        markdown_messages_eq(
+            UndefinedAcronyms::default(),
+            "We use the LSP.\n\nThe Language Server Protocol (LSP) is fast.",
+            &[("LSP", "\"LSP\" is used before it is defined")],
+        );
+    }
+
+    #[test]
+    fn test_ignored() {
+        spans_eq(
+            UndefinedAcronyms::default(),
+            "We use HTML and a URL, not World War II.",
+            &[],
+        );
+        markdown_messages_eq(
+            UndefinedAcronyms::default(),
+            "# LSP support\n\nThe Language Server Protocol (LSP) is fast.",
+            &[],
+        );
+
+        let mut rule = UndefinedAcronyms::default();
+        rule.configure(&toml::toml! { allow = ["lsp"] }).unwrap();
+        spans_eq(rule, "We use the LSP.", &[]);
+
+        let mut rule = UndefinedAcronyms::default();
+        assert!(rule
+            .configure(&toml::toml! { acronyms = ["LSP"] })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+
+    #[test]
+    fn test_incremental() {
+        let parser = PlaintextParser::default();
+        let ruleset = RuleSet::new(vec![Box::new(UndefinedAcronyms::default())], Vec::new());
+        let input = "The Language Server Protocol (LSP) is fast.\n\nWe use the LSP.";
+        let edited = "The protocol is fast.\n\nWe use the LSP.";
+
+        let doc = Document::new(&parser, input);
+        let results = ruleset.apply(&doc);
+        assert_eq!(results.iter_warnings().count(), 0);
+
+        // Removing the definition adds a warning to the unchanged block.
+        let (updated, changes) = doc.update(&parser, Edit::between(input, edited), edited);
+        let results = ruleset.apply_incremental(&updated, &changes, &results);
+        assert_eq!(
+            results
+                .iter_warnings()
+                .map(|warning| warning.span.as_str(edited))
+                .collect::<Vec<_>>(),
+            vec!["LSP"]
+        );
+    }
+}
@@ -2,13 +2,16 @@ use pastelito_model::Tag;
 
 use crate::{
     matcher::Matcher,
-    rule::{MatcherRule, WarningBuilder, WarningsBuilder},
+    rule::{MatcherRule, Severity, WarningBuilder, WarningsBuilder},
     Word,
 };
 
 pub struct WeakIng;
 
 impl MatcherRule for WeakIng {
+    const ID: &'static str = "weak-ing";
+    const SEVERITY: Severity = Severity::Info;
+
     fn matcher() -> impl Matcher {
         (Tag::Modal, "be", Tag::VerbGerundOrPresentParticiple)
     }
@@ -1,15 +1,16 @@
 use pastelito_model::Tag;
 
 use crate::{
-    matcher::{AndS, IgnoreCase, Matcher, OneOfS},
-    rule::{MatcherRule, WarningBuilder, WarningsBuilder},
-    Word,
+    matcher::{AndS, IgnoreCase, OneOfS, SingleWordPattern},
+    phrases::match_case,
+    rule::{Rule, WarningBuilder, WarningsBuilder},
+    ByteSpan, Document, Word,
 };
 
 pub struct WeaselWords;
 
-impl MatcherRule for WeaselWords {
-    fn matcher() -> impl Matcher {
+impl WeaselWords {
+    fn pattern() -> impl SingleWordPattern {
         AndS(
             Tag::Adverb,
             OneOfS([
@@ -33,19 +34,78 @@ impl MatcherRule for WeaselWords {
             ]),
         )
     }
+}
 
-    fn on_match(words: &[Word], warnings: &mut WarningsBuilder) {
-        warnings.add_warning(
-            WarningBuilder::new(words)
-                .message("Weasel words".into())
-                .build(),
-        );
+/// The span to replace to remove `words[i]`, including the whitespace around
+/// it and a comma directly after it, and the replacement.
+///
+/// The whitespace before the word is deleted, unless it starts a sentence, in
+/// which case the whitespace after the word is deleted instead. If the word
+/// starts a sentence, the next word is capitalized to match it if it is all
+/// lowercase, e.g. "Basically, it was" becomes "It was", but "Basically, iOS
+/// is" becomes "iOS is".
+fn deletion(words: &[Word], i: usize, is_sentence_start: bool) -> (ByteSpan, String) {
+    let (last, next) = match words.get(i + 1) {
+        Some(comma) if comma.as_str() == "," => (i + 1, i + 2),
+        _ => (i, i + 1),
+    };
+
+    if !is_sentence_start && i > 0 {
+        let span =
+            ByteSpan::new_unchecked(words[i - 1].as_span().end(), words[last].as_span().end());
+        return (span, String::new());
+    }
+
+    match words.get(next) {
+        Some(next) => (
+            ByteSpan::new_unchecked(words[i].as_span().start(), next.as_span().end()),
+            if next.as_str().chars().any(char::is_uppercase) {
+                next.as_str().to_string()
+            } else {
+                match_case(words[i].as_str(), next.as_str())
+            },
+        ),
+        None => (
+            ByteSpan::new_unchecked(words[i].as_span().start(), words[last].as_span().end()),
+            String::new(),
+        ),
+    }
+}
+
+/// `WeaselWords` is not a `MatcherRule`, as the suggested fix depends on the
+/// words around the match.
+impl Rule for WeaselWords {
+    fn id(&self) -> &str {
+        "weasel-words"
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        let pattern = Self::pattern();
+        for block in doc.iter() {
+            let words = block.as_slice();
+            let sentence_starts = block
+                .sentences()
+                .map(|sentence| sentence[0].as_offset())
+                .collect::<Vec<_>>();
+            for (i, word) in words.iter().enumerate() {
+                if pattern.matches_word(word) {
+                    let is_sentence_start = sentence_starts.contains(&word.as_offset());
+                    let (span, replacement) = deletion(words, i, is_sentence_start);
+                    warnings.add_warning(
+                        WarningBuilder::new(std::slice::from_ref(word))
+                            .message("Weasel words".into())
+                            .suggestion(span, replacement)
+                            .build(),
+                    );
+                }
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::rule::test::rule_eq;
+    use crate::rule::test::{fix_eq, rule_eq};
 
     use super::WeaselWords;
 
@@ -54,4 +114,19 @@ mod tests {
         rule_eq(WeaselWords, "It was quite complex.", 1);
         rule_eq(WeaselWords, "Basically, it was blah.", 1);
     }
+
+    #[test]
+    fn test_fix() {
+        fix_eq(WeaselWords, "It was quite complex.", "It was complex.");
+        fix_eq(WeaselWords, "Basically, it was blah.", "It was blah.");
+        fix_eq(WeaselWords, "It was, basically, blah.", "It was, blah.");
+        fix_eq(
+            WeaselWords,
+            "It rained. Basically, it was blah.",
+            "It rained. It was blah.",
+        );
+        fix_eq(WeaselWords, "Really it was blah.", "It was blah.");
+        fix_eq(WeaselWords, "Basically, iOS is good.", "iOS is good.");
+        fix_eq(WeaselWords, "So, basically, it was.", "So, it was.");
+    }
 }
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::{
+    phrases::{match_case, Phrases},
+    rule::{parse_options, Rule, RuleOptions, WarningBuilder, WarningsBuilder},
+    ByteSpan, Document,
+};
+
+/// The bundled dictionary, mapping each wordy phrase to its replacement.
+static WORDY_PHRASES: &str = include_str!("data/wordy_phrases.toml");
+
+/// The options for `WordyPhrases`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Options {
+    /// More phrases, added to the bundled dictionary.
+    phrases: BTreeMap<String, String>,
+}
+
+/// Flag wordy phrases, and suggest a concise replacement.
+///
+/// The phrases come from a bundled dictionary, which can be extended with the
+/// `phrases` option.
+#[derive(Clone, Debug)]
+pub struct WordyPhrases {
+    phrases: Phrases<String>,
+}
+
+impl WordyPhrases {
+    /// Add `phrases` to the dictionary, replacing any existing replacements.
+    fn extend(&mut self, phrases: BTreeMap<String, String>) -> Result<(), String> {
+        for (phrase, replacement) in phrases {
+            if replacement.trim().is_empty() {
+                return Err(format!("empty replacement for {:?}", phrase));
+            }
+            self.phrases.insert(&phrase, replacement);
+        }
+        Ok(())
+    }
+}
+
+impl Default for WordyPhrases {
+    fn default() -> Self {
+        let bundled: BTreeMap<String, String> =
+            toml::from_str(WORDY_PHRASES).expect("Internal error: invalid wordy_phrases.toml");
+
+        let mut rule = WordyPhrases {
+            phrases: Phrases::default(),
+        };
+        rule.extend(bundled)
+            .expect("Internal error: invalid wordy_phrases.toml");
+        rule
+    }
+}
+
+impl Rule for WordyPhrases {
+    fn id(&self) -> &str {
+        "wordy-phrases"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        let options: Options = parse_options(options)?;
+        self.extend(options.phrases)
+    }
+
+    fn apply(&self, doc: &Document, warnings: &mut WarningsBuilder) {
+        for block in doc.iter() {
+            for (words, replacement) in self.phrases.find_all(block.as_slice()) {
+                let span: ByteSpan = words.into();
+                let matched = span.as_str(doc.input());
+                warnings.add_warning(
+                    WarningBuilder::new(words)
+                        .message(format!("Prefer \"{}\" over \"{}\"", replacement, matched))
+                        .suggestion(span, match_case(matched, replacement))
+                        .build(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::rule::{
+        test::{fix_eq, messages_eq, rule_eq, spans_eq},
+        Rule,
+    };
+
+    use super::{WordyPhrases, WORDY_PHRASES};
+
+    #[test]
+    fn test() {
+        rule_eq(WordyPhrases::default(), "We left in order to eat.", 1);
+        rule_eq(
+            WordyPhrases::default(),
+            "Due to the fact that it rained, we stayed in at this point in time.",
+            2,
+        );
+        rule_eq(WordyPhrases::default(), "We left to eat.", 0);
+        messages_eq(
+            WordyPhrases::default(),
+            "In Order To eat, we left.",
+            &[("In Order To", "Prefer \"to\" over \"In Order To\"")],
+        );
+    }
+
+    #[test]
+    fn test_fix() {
+        fix_eq(
+            WordyPhrases::default(),
+            "We left in order to eat.",
+            "We left to eat.",
+        );
+        fix_eq(
+            WordyPhrases::default(),
+            "Due to the fact that it rained, we left.",
+            "Because it rained, we left.",
+        );
+        fix_eq(
+            WordyPhrases::default(),
+            "We stopped at this point in time.",
+            "We stopped now.",
+        );
+    }
+
+    /// Phrases whose replacement would be ungrammatical or change the meaning
+    /// aren't bundled.
+    #[test]
+    fn test_not_flagged() {
+        for input in [
+            "You will be able to log in.",
+            "The numbers don't add up.",
+            "It is not the same as before.",
+            "It was a period of calm.",
+            "We were not able to fill out the form.",
+        ] {
+            spans_eq(WordyPhrases::default(), input, &[]);
+        }
+        fix_eq(
+            WordyPhrases::default(),
+            "It rained for a period of days.",
+            "It rained for days.",
+        );
+    }
+
+    /// Every bundled phrase matches itself.
+    #[test]
+    fn test_bundled() {
+        let bundled: BTreeMap<String, String> = toml::from_str(WORDY_PHRASES).unwrap();
+        assert!(bundled.len() > 300);
+
+        let rule = WordyPhrases::default();
+        for phrase in bundled.keys() {
+            spans_eq(rule.clone(), phrase, &[phrase]);
+        }
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut rule = WordyPhrases::default();
+        rule.configure(&toml::toml! {
+            [phrases]
+            "in a nutshell" = "briefly"
+            "in order to" = "so as to"
+        })
+        .unwrap();
+        fix_eq(
+            rule.clone(),
+            "In a nutshell, we left in order to eat.",
+            "Briefly, we left so as to eat.",
+        );
+
+        assert!(rule
+            .configure(&toml::toml! {
+                [phrases]
+                "in a nutshell" = ""
+            })
+            .unwrap_err()
+            .contains("empty replacement"));
+        assert!(rule
+            .configure(&toml::toml! { words = [] })
+            .unwrap_err()
+            .contains("unknown field"));
+    }
+}
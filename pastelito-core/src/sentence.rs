@@ -0,0 +1,210 @@
+//! Split blocks into sentences.
+use pastelito_model::Tag;
+
+use crate::Word;
+
+/// Abbreviations which are followed by a full stop, but never end a sentence.
+///
+/// These are compared ignoring ASCII case, without the final full stop.
+const ABBREVIATIONS: &[&str] = &["cf", "e.g", "eg", "i.e", "ie", "vs"];
+
+/// Abbreviations which may be in the middle of a sentence, e.g. "etc." in
+/// "apples, pears, etc. are fruit", or may end it, e.g. "apples, pears, etc.".
+const AMBIGUOUS_ABBREVIATIONS: &[&str] = &[
+    "approx", "co", "corp", "dept", "esp", "est", "etc", "fig", "inc", "jr", "ltd", "no", "sr",
+    "vol",
+];
+
+/// Abbreviations which are followed by a name, e.g. "Dr. Smith".
+const TITLES: &[&str] = &["dr", "mr", "mrs", "ms", "mt", "prof", "st"];
+
+/// An iterator over the sentences in a block, created by `Block::sentences`.
+///
+/// Each sentence is a non-empty slice of the block's words.
+#[derive(Clone, Debug)]
+pub struct Sentences<'a, 'input> {
+    words: &'a [Word<'input>],
+}
+
+impl<'a, 'input> Sentences<'a, 'input> {
+    pub(crate) fn new(words: &'a [Word<'input>]) -> Self {
+        Sentences { words }
+    }
+}
+
+impl<'a, 'input> Iterator for Sentences<'a, 'input> {
+    type Item = &'a [Word<'input>];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.words.is_empty() {
+            return None;
+        }
+
+        let len = sentence_len(self.words);
+        let (sentence, rest) = self.words.split_at(len);
+        self.words = rest;
+        Some(sentence)
+    }
+}
+
+/// Find the number of words in the first sentence of `words`.
+///
+/// A sentence ends with a word tagged `Tag::EndOfSentence`, unless it's a full
+/// stop directly after an abbreviation or an initial, which doesn't end the
+/// sentence. Any closing quotes or brackets directly after the end of the
+/// sentence are part of the sentence.
+fn sentence_len(words: &[Word]) -> usize {
+    for (i, word) in words.iter().enumerate() {
+        if word.tag() != Some(Tag::EndOfSentence) {
+            continue;
+        }
+
+        if word.as_str() == "." && i > 0 && is_abbreviation(&words[i - 1], word, words.get(i + 1)) {
+            continue;
+        }
+
+        let mut end = i + 1;
+        while end < words.len() && is_closing(&words[end - 1], &words[end]) {
+            end += 1;
+        }
+        return end;
+    }
+
+    words.len()
+}
+
+/// Is `previous` an abbreviation or initial, directly followed by `stop`,
+/// which doesn't end the sentence?
+///
+/// Many abbreviations can also end a sentence, e.g. "etc." or "no.", so this
+/// depends on the `next` word. These must be followed by a word starting with
+/// a lowercase letter, or a number. A title or initial may also be followed by
+/// a name, e.g. "Dr. Smith" or "J. R. R. Tolkien".
+fn is_abbreviation(previous: &Word, stop: &Word, next: Option<&Word>) -> bool {
+    if previous.as_span().end() != stop.as_offset() {
+        return false;
+    }
+    let Some(next) = next else {
+        return false;
+    };
+
+    let is_one_of = |abbreviations: &[&str]| {
+        abbreviations
+            .iter()
+            .any(|abbreviation| abbreviation.eq_ignore_ascii_case(previous.as_str()))
+    };
+    let mut chars = previous.as_str().chars();
+    let is_initial = matches!((chars.next(), chars.next()), (Some(c), None) if c.is_uppercase());
+    let is_title = is_initial || is_one_of(TITLES);
+    if is_one_of(ABBREVIATIONS) {
+        return true;
+    } else if !is_title && !is_one_of(AMBIGUOUS_ABBREVIATIONS) {
+        return false;
+    }
+
+    let continues = next.as_str().starts_with(char::is_lowercase)
+        || next.as_str().starts_with(|c: char| c.is_ascii_digit())
+        || next.tag() == Some(Tag::CardinalNumber);
+    continues || (is_title && next.is_proper_noun())
+}
+
+/// Is `word` a closing quote or bracket directly after `previous`?
+fn is_closing(previous: &Word, word: &Word) -> bool {
+    previous.as_span().end() == word.as_offset()
+        && matches!(word.as_str(), "\"" | "'" | ")" | "]" | "”" | "’")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{doc::Document, parsers::MarkdownParser, ByteSpan};
+
+    fn eq(input: &str, expected: Vec<&str>) {
+        let doc = Document::new(&MarkdownParser::default(), input);
+        let sentences = doc
+            .iter()
+            .flat_map(|block| block.sentences())
+            .map(|sentence| {
+                let span: ByteSpan = sentence.into();
+                span.as_str(input)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sentences, expected, "input={:?}", input);
+    }
+
+    #[test]
+    fn test_empty() {
+        eq("", vec![]);
+    }
+
+    #[test]
+    fn test_sentences() {
+        eq("The cat sat.", vec!["The cat sat."]);
+        eq("The cat sat", vec!["The cat sat"]);
+        eq(
+            "The cat sat. It was big! Was it?",
+            vec!["The cat sat.", "It was big!", "Was it?"],
+        );
+        eq("The cat sat. It", vec!["The cat sat.", "It"]);
+    }
+
+    #[test]
+    fn test_blocks() {
+        eq(
+            "# The title\n\nThe cat sat.\n\n* It was big",
+            vec!["The title", "The cat sat.", "It was big"],
+        );
+    }
+
+    #[test]
+    fn test_abbreviations() {
+        eq(
+            "Dr. Smith sat, e.g. on the mat. It was big.",
+            vec!["Dr. Smith sat, e.g. on the mat.", "It was big."],
+        );
+        eq("J. R. R. Tolkien sat.", vec!["J. R. R. Tolkien sat."]);
+        eq(
+            "It is item no. 5 on the list.",
+            vec!["It is item no. 5 on the list."],
+        );
+        eq(
+            "We ate apples, pears, etc. and then left.",
+            vec!["We ate apples, pears, etc. and then left."],
+        );
+        eq("We met Prof. Jones.", vec!["We met Prof. Jones."]);
+        // A full stop after a space is not an abbreviation.
+        eq("It was Dr . It sat.", vec!["It was Dr .", "It sat."]);
+    }
+
+    #[test]
+    fn test_abbreviations_at_end() {
+        eq(
+            "The answer is no. We left.",
+            vec!["The answer is no.", "We left."],
+        );
+        eq(
+            "We ate apples, pears, etc. Then we left.",
+            vec!["We ate apples, pears, etc.", "Then we left."],
+        );
+        eq("So did I. We left.", vec!["So did I.", "We left."]);
+        eq(
+            "It is on Main St. We left.",
+            vec!["It is on Main St.", "We left."],
+        );
+        eq(
+            "It was founded in 1990 as Acme Co.",
+            vec!["It was founded in 1990 as Acme Co."],
+        );
+    }
+
+    #[test]
+    fn test_closing() {
+        eq(
+            "He said \"the cat sat.\" It was big.",
+            vec!["He said \"the cat sat.\"", "It was big."],
+        );
+        eq(
+            "(The cat sat.) It was big.",
+            vec!["(The cat sat.)", "It was big."],
+        );
+    }
+}
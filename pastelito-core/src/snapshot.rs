@@ -0,0 +1,109 @@
+use self_cell::self_cell;
+
+use crate::{
+    doc::{Document, Edit, Parser},
+    rule::{Results, RuleSet},
+};
+
+/// A parsed document and the results of applying a rule set to it.
+struct Analysed<'input> {
+    doc: Document<'input>,
+    results: Results<'input>,
+}
+
+self_cell!(
+    struct SnapshotCell {
+        owner: String,
+
+        #[covariant]
+        dependent: Analysed,
+    }
+);
+
+/// An owned snapshot of a document: the input data, the parsed document and
+/// the results of applying a rule set.
+///
+/// `Document` and `Results` borrow the input data, which makes them awkward to
+/// keep around between edits. A `Snapshot` owns the input data so it can be
+/// stored by editor integrations and updated incrementally as the user types.
+pub struct Snapshot(SnapshotCell);
+
+impl Snapshot {
+    /// Parse `input` and apply `ruleset` to it.
+    pub fn new(parser: &impl Parser, ruleset: &RuleSet, input: String) -> Self {
+        Snapshot(SnapshotCell::new(input, |input| {
+            let doc = Document::new(parser, input);
+            let results = ruleset.apply(&doc);
+            Analysed { doc, results }
+        }))
+    }
+
+    /// Create a new snapshot for an edited version of this snapshot's input.
+    ///
+    /// The edit is found by comparing `input` with the previous input, and
+    /// only the blocks affected by the edit are re-tagged and re-checked. See
+    /// `Document::update` and `RuleSet::apply_incremental`.
+    ///
+    /// `parser` and `ruleset` must be the same as those used to create this
+    /// snapshot.
+    pub fn update(&self, parser: &impl Parser, ruleset: &RuleSet, input: String) -> Self {
+        let previous = self.0.borrow_dependent();
+        let edit = Edit::between(self.input(), &input);
+
+        Snapshot(SnapshotCell::new(input, |input| {
+            let (doc, changes) = previous.doc.update(parser, edit, input);
+            let results = ruleset.apply_incremental(&doc, &changes, &previous.results);
+            Analysed { doc, results }
+        }))
+    }
+
+    /// Get the input data.
+    pub fn input(&self) -> &str {
+        self.0.borrow_owner()
+    }
+
+    /// Get the parsed document.
+    pub fn document(&self) -> &Document<'_> {
+        &self.0.borrow_dependent().doc
+    }
+
+    /// Get the results of applying the rule set.
+    pub fn results(&self) -> &Results<'_> {
+        &self.0.borrow_dependent().results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parsers::MarkdownParser, rule::RuleSet};
+
+    use super::Snapshot;
+
+    #[test]
+    fn test_update() {
+        let parser = MarkdownParser::default();
+        let ruleset = RuleSet::default();
+
+        let snapshot = Snapshot::new(
+            &parser,
+            &ruleset,
+            "It was quite big.\n\nThe cat sat.".into(),
+        );
+        assert_eq!(snapshot.results().iter_warnings().count(), 1);
+
+        let snapshot = snapshot.update(
+            &parser,
+            &ruleset,
+            "# Title\n\nIt was quite big.\n\nThe cat sat.".into(),
+        );
+        assert_eq!(
+            snapshot.input(),
+            "# Title\n\nIt was quite big.\n\nThe cat sat."
+        );
+        assert_eq!(snapshot.document().iter().count(), 3);
+
+        let warnings = snapshot.results().iter_warnings().collect::<Vec<_>>();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].span.as_str(snapshot.input()), "quite");
+    }
+}
@@ -2,11 +2,11 @@ use std::{ops::Range, str::SplitWhitespace};
 
 use crate::Word;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
 struct ByteOffset(usize);
 
 /// A span of the underlying input data, represented as byte offsets.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct ByteSpan {
     start: ByteOffset,
     end: ByteOffset,
@@ -73,6 +73,24 @@ impl ByteSpan {
     pub fn end(&self) -> usize {
         self.end.0
     }
+
+    /// Get the length of this span in bytes.
+    pub fn len(&self) -> usize {
+        self.end.0 - self.start.0
+    }
+
+    /// Does this span entirely contain `other`?
+    pub fn contains(&self, other: &ByteSpan) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Move this span so that a position at `from` is now at `to`.
+    ///
+    /// This is used to keep spans up to date after text has been inserted or
+    /// removed earlier in the input data.
+    pub fn moved(&self, from: usize, to: usize) -> Self {
+        ByteSpan::new_unchecked(self.start.0 - from + to, self.end.0 - from + to)
+    }
 }
 
 /// A span of the underlying input data, represented as byte offsets.
@@ -0,0 +1,293 @@
+//! Suppressing warnings with comments in the document.
+//!
+//! Markdown documents can use HTML comments to suppress warnings:
+//!
+//! * `<!-- pastelito-disable-next-line -->` suppresses warnings which start on
+//!   the next line
+//! * `<!-- pastelito-disable -->` suppresses warnings until the next
+//!   `<!-- pastelito-enable -->`, or the end of the document
+//!
+//! Each comment can be followed by a list of rule ids, separated by spaces or
+//! commas, e.g. `<!-- pastelito-disable-next-line passive-construction -->`.
+//! If no rule ids are given, the comment applies to all rules.
+use crate::{rule::Warning, ByteSpan};
+
+const DISABLE_NEXT_LINE: &str = "pastelito-disable-next-line";
+const DISABLE: &str = "pastelito-disable";
+const ENABLE: &str = "pastelito-enable";
+
+/// A region of the document where warnings are suppressed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Region {
+    span: ByteSpan,
+    /// The rule ids to suppress. If this is empty, all rules are suppressed.
+    rule_ids: Vec<String>,
+}
+
+impl Region {
+    fn suppresses(&self, warning: &Warning) -> bool {
+        self.span.start() <= warning.span.start()
+            && warning.span.start() < self.span.end()
+            && (self.rule_ids.is_empty() || self.rule_ids.contains(&warning.rule_id))
+    }
+}
+
+/// The regions of a document where warnings are suppressed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Suppressions {
+    regions: Vec<Region>,
+}
+
+impl Suppressions {
+    /// Are there no suppressed regions?
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Is `warning` suppressed?
+    ///
+    /// A warning is suppressed if it starts inside a region which suppresses
+    /// its rule.
+    pub fn is_suppressed(&self, warning: &Warning) -> bool {
+        self.regions.iter().any(|region| region.suppresses(warning))
+    }
+}
+
+/// Build the `Suppressions` for a document, from the comments in it.
+///
+/// Comments must be passed to `comments` in the order they appear in the
+/// document.
+pub(crate) struct SuppressionsBuilder<'input> {
+    input: &'input str,
+    regions: Vec<Region>,
+    /// The start of the open `pastelito-disable` region for all rules.
+    disabled_all: Option<usize>,
+    /// The start of each open `pastelito-disable` region for specific rules.
+    disabled: Vec<(String, usize)>,
+}
+
+impl<'input> SuppressionsBuilder<'input> {
+    pub(crate) fn new(input: &'input str) -> Self {
+        SuppressionsBuilder {
+            input,
+            regions: Vec::new(),
+            disabled_all: None,
+            disabled: Vec::new(),
+        }
+    }
+
+    /// Handle each HTML comment in `span`, which is a fragment of HTML from the
+    /// document.
+    pub(crate) fn comments(&mut self, span: ByteSpan) {
+        let html = span.as_str(self.input);
+        let mut offset = 0;
+
+        while let Some(start) = html[offset..].find("<!--") {
+            let body_start = offset + start + "<!--".len();
+            let Some(len) = html[body_start..].find("-->") else {
+                break;
+            };
+            let body_end = body_start + len;
+            offset = body_end + "-->".len();
+
+            self.comment(&html[body_start..body_end], span.start() + offset);
+        }
+    }
+
+    /// Handle a single comment with the given body. `end` is the offset of the
+    /// end of the comment in the input.
+    fn comment(&mut self, body: &str, end: usize) {
+        let mut words = body
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty());
+
+        let Some(directive) = words.next() else {
+            return;
+        };
+        let rule_ids = words.map(str::to_string).collect::<Vec<_>>();
+
+        match directive {
+            DISABLE_NEXT_LINE => {
+                let span = self.next_line(end);
+                self.regions.push(Region { span, rule_ids });
+            }
+            DISABLE => {
+                if rule_ids.is_empty() {
+                    self.disabled_all.get_or_insert(end);
+                } else {
+                    for id in rule_ids {
+                        if !self.disabled.iter().any(|(open, _)| *open == id) {
+                            self.disabled.push((id, end));
+                        }
+                    }
+                }
+            }
+            ENABLE => {
+                let start = end - body.len() - "<!---->".len();
+                if rule_ids.is_empty() {
+                    self.close_all(start);
+                } else {
+                    for id in rule_ids {
+                        if let Some(i) = self.disabled.iter().position(|(open, _)| *open == id) {
+                            let (id, open) = self.disabled.remove(i);
+                            self.close(open, start, vec![id]);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The span of the line after the line containing `offset`.
+    fn next_line(&self, offset: usize) -> ByteSpan {
+        let start = match self.input[offset..].find('\n') {
+            Some(i) => offset + i + 1,
+            None => self.input.len(),
+        };
+        let end = match self.input[start..].find('\n') {
+            Some(i) => start + i,
+            None => self.input.len(),
+        };
+        ByteSpan::new_unchecked(start, end)
+    }
+
+    fn close(&mut self, start: usize, end: usize, rule_ids: Vec<String>) {
+        self.regions.push(Region {
+            span: ByteSpan::new_unchecked(start, end),
+            rule_ids,
+        });
+    }
+
+    /// Close all the open `pastelito-disable` regions at `end`.
+    fn close_all(&mut self, end: usize) {
+        if let Some(start) = self.disabled_all.take() {
+            self.close(start, end, Vec::new());
+        }
+        for (id, start) in std::mem::take(&mut self.disabled) {
+            self.close(start, end, vec![id]);
+        }
+    }
+
+    pub(crate) fn build(mut self) -> Suppressions {
+        self.close_all(self.input.len());
+        Suppressions {
+            regions: self.regions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        doc::Document,
+        parsers::MarkdownParser,
+        rule::{RuleSet, Severity, Warning},
+        ByteSpan,
+    };
+
+    use super::SuppressionsBuilder;
+
+    /// The rule ids of the warnings for `input`, using the default rules.
+    fn warnings(input: &str) -> Vec<&'static str> {
+        let doc = Document::new(&MarkdownParser::default(), input);
+        let results = RuleSet::default().apply(&doc);
+        results
+            .iter_warnings()
+            .map(|warning| match warning.rule_id.as_str() {
+                "passive-construction" => "passive",
+                "weasel-words" => "weasel",
+                "repeated-words" => "repeated",
+                other => panic!("unexpected warning {:?}", other),
+            })
+            .collect()
+    }
+
+    fn warning(start: usize, rule_id: &str) -> Warning {
+        Warning {
+            span: ByteSpan::new_unchecked(start, start + 1),
+            message: String::new(),
+            rule_id: rule_id.into(),
+            severity: Severity::Warning,
+            grade: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let input = "<!-- pastelito-disable-next-line a -->\nline two\nline three";
+        let mut builder = SuppressionsBuilder::new(input);
+        builder.comments(ByteSpan::new_unchecked(0, 38));
+        let suppressions = builder.build();
+
+        assert!(!suppressions.is_suppressed(&warning(39, "b")));
+        assert!(suppressions.is_suppressed(&warning(39, "a")));
+        assert!(suppressions.is_suppressed(&warning(46, "a")));
+        assert!(!suppressions.is_suppressed(&warning(48, "a")));
+        assert!(!suppressions.is_suppressed(&warning(0, "a")));
+    }
+
+    #[test]
+    fn test_next_line() {
+        let input = "It is is quite good.\n\n\
+                     <!-- pastelito-disable-next-line -->\n\
+                     It is is quite good.\n\n\
+                     It is is quite good.";
+        assert_eq!(
+            warnings(input),
+            vec!["repeated", "weasel", "repeated", "weasel"]
+        );
+
+        let input = "<!-- pastelito-disable-next-line weasel-words -->\n\
+                     It is is quite good.";
+        assert_eq!(warnings(input), vec!["repeated"]);
+
+        let input = "<!-- pastelito-disable-next-line weasel-words, repeated-words -->\n\
+                     It is is quite good.";
+        assert!(warnings(input).is_empty());
+    }
+
+    #[test]
+    fn test_inline_comment() {
+        let input = "It is quite good. <!-- pastelito-disable-next-line -->\n\
+                     It is is good.\n\
+                     That is is good.";
+        assert_eq!(warnings(input), vec!["weasel", "repeated"]);
+    }
+
+    #[test]
+    fn test_disable_enable() {
+        let input = "It is is good.\n\n\
+                     <!-- pastelito-disable -->\n\n\
+                     It is is quite good.\n\n\
+                     It is is quite good.\n\n\
+                     <!-- pastelito-enable -->\n\n\
+                     It is is good.";
+        assert_eq!(warnings(input), vec!["repeated", "repeated"]);
+
+        // Without an `enable`, the region continues to the end of the document.
+        let input = "It is is good.\n\n\
+                     <!-- pastelito-disable repeated-words -->\n\n\
+                     It is is quite good.";
+        assert_eq!(warnings(input), vec!["repeated", "weasel"]);
+
+        // Rules can be enabled individually.
+        let input = "<!-- pastelito-disable repeated-words weasel-words -->\n\n\
+                     It is is quite good.\n\n\
+                     <!-- pastelito-enable weasel-words -->\n\n\
+                     It is is quite good.";
+        assert_eq!(warnings(input), vec!["weasel"]);
+    }
+
+    #[test]
+    fn test_other_comments() {
+        let input = "<!-- a comment -->\n\
+                     It is is good.\n\n\
+                     <!-- pastelito-unknown -->\n\
+                     It is is good.\n\n\
+                     ```\n<!-- pastelito-disable -->\n```\n\n\
+                     It is is good.";
+        assert_eq!(warnings(input), vec!["repeated", "repeated", "repeated"]);
+    }
+}
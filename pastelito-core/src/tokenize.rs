@@ -2,6 +2,10 @@ use crate::block::{Block, Word};
 use crate::span::{ByteSpan, FullByteSpan};
 use smallvec::SmallVec;
 
+/// The contractions which are split from the end of a word, e.g. "is" and
+/// "n't" in "isn't".
+pub(crate) const CONTRACTIONS: &[&str] = &["'ll", "'s", "'re", "'m", "n't"];
+
 /// A tokenizer that splits a block of text into words.
 #[derive(Default)]
 pub struct Tokenizer {}
@@ -75,8 +79,7 @@ impl Tokenizer {
     }
 
     fn has_contraction(span: FullByteSpan) -> Option<(FullByteSpan, FullByteSpan, FullByteSpan)> {
-        let contractions = ["'ll", "'s", "'re", "'m", "n't"];
-        contractions
+        CONTRACTIONS
             .iter()
             .filter_map(|contraction| span.split3(contraction))
             .next()
@@ -0,0 +1,294 @@
+use pastelito_core::{
+    doc::Parser,
+    parsers::{MarkdownParser, PlaintextParser},
+    rule::{MeasureKey, RuleSet, Severity},
+    LineCharRange, Snapshot,
+};
+use serde_json::json;
+use strum::VariantArray as _;
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range, SemanticToken,
+    SemanticTokenType, SemanticTokensLegend,
+};
+
+/// The LSP severity for a warning.
+fn to_severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+        Severity::Hint => DiagnosticSeverity::HINT,
+    }
+}
+
+/// The name of the semantic token type used for each `MeasureKey`.
+fn token_type_name(key: MeasureKey) -> &'static str {
+    match key {
+        MeasureKey::AbstractNouns => "abstractNoun",
+        MeasureKey::AcademicAdWords => "academicAdWord",
+        MeasureKey::Adjectives => "adjective",
+        MeasureKey::BeVerbs => "beVerb",
+        MeasureKey::Prepositions => "preposition",
+    }
+}
+
+/// The semantic tokens legend advertised to the client.
+///
+/// The index of each token type is the `MeasureKey` discriminant, so a
+/// measurement can be converted to a token type with `key as u32`.
+pub(crate) fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: MeasureKey::VARIANTS
+            .iter()
+            .map(|key| SemanticTokenType::new(token_type_name(*key)))
+            .collect(),
+        token_modifiers: Vec::new(),
+    }
+}
+
+fn to_range(range: LineCharRange) -> Range {
+    Range::new(
+        Position::new(range.start_line, range.start_char_utf16),
+        Position::new(range.end_line, range.end_char_utf16),
+    )
+}
+
+/// The format of a document, based on the client's language id.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Format {
+    Markdown,
+    Plaintext,
+}
+
+impl Format {
+    pub(crate) fn of_language_id(language_id: &str) -> Self {
+        match language_id {
+            "plaintext" | "text" => Format::Plaintext,
+            _ => Format::Markdown,
+        }
+    }
+
+    /// Parse `input` and apply `ruleset`.
+    ///
+    /// If a snapshot of a previous version of the document is available, only
+    /// the parts of the document which changed are re-analysed.
+    pub(crate) fn snapshot(
+        self,
+        ruleset: &RuleSet,
+        previous: Option<&Snapshot>,
+        input: String,
+    ) -> Snapshot {
+        fn snapshot_with(
+            parser: &impl Parser,
+            ruleset: &RuleSet,
+            previous: Option<&Snapshot>,
+            input: String,
+        ) -> Snapshot {
+            match previous {
+                Some(previous) => previous.update(parser, ruleset, input),
+                None => Snapshot::new(parser, ruleset, input),
+            }
+        }
+
+        match self {
+            Format::Markdown => snapshot_with(&MarkdownParser::default(), ruleset, previous, input),
+            Format::Plaintext => {
+                snapshot_with(&PlaintextParser::default(), ruleset, previous, input)
+            }
+        }
+    }
+}
+
+/// The results of analysing a single document, converted to LSP types.
+#[derive(Debug, Default)]
+pub(crate) struct Analysis {
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    pub(crate) tokens: Vec<SemanticToken>,
+}
+
+impl Analysis {
+    /// Convert the results of a snapshot to LSP types.
+    pub(crate) fn new(snapshot: &Snapshot) -> Self {
+        let results = snapshot.results();
+
+        let diagnostics = results
+            .iter_warnings_with_ranges()
+            .map(|(range, warning)| Diagnostic {
+                range: to_range(range),
+                severity: Some(to_severity(warning.severity)),
+                code: Some(NumberOrString::String(warning.rule_id.clone())),
+                source: Some("pastelito".into()),
+                message: warning.message.clone(),
+                // Editors can use the grade to shade hard sentences.
+                data: warning.grade.map(|grade| json!({ "grade": grade.value() })),
+                ..Default::default()
+            })
+            .collect();
+
+        let tokens = encode_tokens(
+            results
+                .iter_measurements_with_ranges()
+                .map(|(range, measurement)| (range, measurement.key)),
+        );
+
+        Analysis {
+            diagnostics,
+            tokens,
+        }
+    }
+}
+
+/// Encode measurements as relative semantic tokens.
+///
+/// `measurements` must be sorted by range. Semantic tokens can't overlap, so if
+/// a word has several measurements then only the first one is used. This
+/// matches the precedence used by the VSCode extension. Measurements which
+/// span multiple lines are skipped.
+fn encode_tokens(
+    measurements: impl Iterator<Item = (LineCharRange, MeasureKey)>,
+) -> Vec<SemanticToken> {
+    let mut tokens = Vec::new();
+    let mut prev_line = 0;
+    let mut prev_start = 0;
+    let mut prev_end: Option<(u32, u32)> = None;
+
+    for (range, key) in measurements {
+        if range.start_line != range.end_line {
+            continue;
+        }
+
+        // Skip anything overlapping the previous token.
+        if let Some((line, end)) = prev_end {
+            if range.start_line == line && range.start_char_utf16 < end {
+                continue;
+            }
+        }
+
+        let delta_line = range.start_line - prev_line;
+        let delta_start = if delta_line == 0 {
+            range.start_char_utf16 - prev_start
+        } else {
+            range.start_char_utf16
+        };
+
+        tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: range.end_char_utf16 - range.start_char_utf16,
+            token_type: key as u32,
+            token_modifiers_bitset: 0,
+        });
+
+        prev_line = range.start_line;
+        prev_start = range.start_char_utf16;
+        prev_end = Some((range.start_line, range.end_char_utf16));
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(delta_line: u32, delta_start: u32, length: u32, key: MeasureKey) -> SemanticToken {
+        SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type: key as u32,
+            token_modifiers_bitset: 0,
+        }
+    }
+
+    #[test]
+    fn test_legend() {
+        let legend = legend();
+        for key in MeasureKey::VARIANTS {
+            assert_eq!(
+                legend.token_types[*key as usize].as_str(),
+                token_type_name(*key)
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_tokens() {
+        let tokens = encode_tokens(
+            [
+                (LineCharRange::new(0, 4, 0, 7), MeasureKey::Adjectives),
+                // Overlaps the previous token, so is dropped.
+                (LineCharRange::new(0, 4, 0, 7), MeasureKey::BeVerbs),
+                (LineCharRange::new(0, 10, 0, 12), MeasureKey::Prepositions),
+                // Multi-line ranges are dropped.
+                (LineCharRange::new(1, 3, 2, 1), MeasureKey::Adjectives),
+                (LineCharRange::new(2, 3, 2, 5), MeasureKey::AbstractNouns),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(
+            tokens,
+            vec![
+                token(0, 4, 3, MeasureKey::Adjectives),
+                token(0, 6, 2, MeasureKey::Prepositions),
+                token(2, 3, 2, MeasureKey::AbstractNouns),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analysis() {
+        let ruleset = RuleSet::default();
+        let snapshot = Format::Markdown.snapshot(&ruleset, None, "# Title\n\nIt was".into());
+        let snapshot = Format::Markdown.snapshot(
+            &ruleset,
+            Some(&snapshot),
+            "# Title\n\nIt was quite complex.".into(),
+        );
+        let analysis = Analysis::new(&snapshot);
+        assert_eq!(analysis.diagnostics.len(), 1);
+        assert_eq!(
+            analysis.diagnostics[0].range,
+            Range::new(Position::new(2, 7), Position::new(2, 12))
+        );
+        assert_eq!(
+            analysis.diagnostics[0].code,
+            Some(NumberOrString::String("weasel-words".into()))
+        );
+        assert_eq!(
+            analysis.diagnostics[0].severity,
+            Some(DiagnosticSeverity::WARNING)
+        );
+        assert_eq!(analysis.diagnostics[0].data, None);
+        assert!(!analysis.tokens.is_empty());
+    }
+
+    #[test]
+    fn test_analysis_grade() {
+        let ruleset = RuleSet::default();
+        let snapshot = Format::Markdown.snapshot(
+            &ruleset,
+            None,
+            "The committee considered several complicated proposals regarding the \
+             administration of educational institutions throughout the region."
+                .into(),
+        );
+        let analysis = Analysis::new(&snapshot);
+        assert_eq!(analysis.diagnostics.len(), 1);
+        assert_eq!(
+            analysis.diagnostics[0].code,
+            Some(NumberOrString::String("hard-sentences".into()))
+        );
+        let grade = analysis.diagnostics[0].data.as_ref().unwrap()["grade"]
+            .as_f64()
+            .unwrap();
+        assert!(grade > 14.0);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Format::of_language_id("markdown"), Format::Markdown);
+        assert_eq!(Format::of_language_id("plaintext"), Format::Plaintext);
+    }
+}
@@ -0,0 +1,14 @@
+mod analysis;
+mod server;
+mod text;
+
+use tower_lsp::{LspService, Server};
+
+#[tokio::main]
+async fn main() {
+    let stdin = tokio::io::stdin();
+    let stdout = tokio::io::stdout();
+
+    let (service, socket) = LspService::new(server::Backend::new);
+    Server::new(stdin, stdout, socket).serve(service).await;
+}
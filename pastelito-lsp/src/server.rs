@@ -0,0 +1,249 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use pastelito_core::{config::Config, rule::RuleSet, Snapshot};
+use tower_lsp::{
+    jsonrpc::Result,
+    lsp_types::{
+        DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+        InitializeParams, InitializeResult, InitializedParams, MessageType, SemanticTokens,
+        SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensParams,
+        SemanticTokensResult, ServerCapabilities, ServerInfo, TextDocumentSyncCapability,
+        TextDocumentSyncKind, Url,
+    },
+    Client, LanguageServer,
+};
+
+use crate::{
+    analysis::{legend, Analysis, Format},
+    text::TextDocument,
+};
+
+/// An open document and the most recent analysis of it.
+struct OpenDocument {
+    text: TextDocument,
+    format: Format,
+    /// The rules for this document, from the `pastelito.toml` files which
+    /// apply to it when it was opened.
+    ruleset: Arc<RuleSet>,
+    /// The most recent snapshot of the document, and its version. Snapshots
+    /// are updated incrementally when the document changes.
+    snapshot: Option<(i32, Arc<Snapshot>)>,
+    /// The analysis, and the version of the document it was computed from.
+    analysis: Option<(i32, Arc<Analysis>)>,
+}
+
+/// The pastelito language server.
+pub(crate) struct Backend {
+    client: Client,
+    /// The rule set for each set of config files, so that documents which
+    /// share the same config also share a rule set.
+    rulesets: Mutex<HashMap<Vec<PathBuf>, Arc<RuleSet>>>,
+    documents: Mutex<HashMap<Url, OpenDocument>>,
+}
+
+impl Backend {
+    pub(crate) fn new(client: Client) -> Self {
+        Backend {
+            client,
+            rulesets: Mutex::new(HashMap::new()),
+            documents: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the rule set for a document, from the `pastelito.toml` files which
+    /// apply to it.
+    ///
+    /// Documents which are not files use the default config. If the config is
+    /// invalid, an error is shown and the default config is used.
+    async fn ruleset(&self, uri: &Url) -> Arc<RuleSet> {
+        let config = match uri.to_file_path() {
+            Ok(path) => Config::discover(&path),
+            Err(()) => Ok(Config::default()),
+        };
+
+        let config = config.and_then(|config| {
+            let mut rulesets = self.rulesets.lock().unwrap();
+            if let Some(ruleset) = rulesets.get(config.sources()) {
+                return Ok(ruleset.clone());
+            }
+
+            let ruleset = Arc::new(config.build_ruleset()?);
+            rulesets.insert(config.sources().to_vec(), ruleset.clone());
+            Ok(ruleset)
+        });
+
+        match config {
+            Ok(ruleset) => ruleset,
+            Err(err) => {
+                self.client
+                    .show_message(MessageType::ERROR, format!("pastelito: {}", err))
+                    .await;
+                Arc::new(RuleSet::default())
+            }
+        }
+    }
+
+    /// Get the analysis for the current version of a document.
+    ///
+    /// The analysis is cached, so this only re-analyses the document if it has
+    /// changed. Only the blocks affected by the changes since the previous
+    /// snapshot are re-analysed. Analysis runs on a blocking thread so that
+    /// large documents don't stall the message loop.
+    async fn analysis(&self, uri: &Url) -> Option<(i32, Arc<Analysis>)> {
+        let (text, format, ruleset, version, previous) = {
+            let documents = self.documents.lock().unwrap();
+            let doc = documents.get(uri)?;
+
+            if let Some((version, analysis)) = &doc.analysis {
+                if *version == doc.text.version() {
+                    return Some((*version, analysis.clone()));
+                }
+            }
+
+            let previous = doc.snapshot.as_ref().map(|(_, snapshot)| snapshot.clone());
+            (
+                doc.text.text().to_owned(),
+                doc.format,
+                doc.ruleset.clone(),
+                doc.text.version(),
+                previous,
+            )
+        };
+
+        let (snapshot, analysis) = tokio::task::spawn_blocking(move || {
+            let snapshot = format.snapshot(&ruleset, previous.as_deref(), text);
+            let analysis = Analysis::new(&snapshot);
+            (Arc::new(snapshot), Arc::new(analysis))
+        })
+        .await
+        .ok()?;
+
+        let mut documents = self.documents.lock().unwrap();
+        if let Some(doc) = documents.get_mut(uri) {
+            // Keep the newest snapshot as the base for the next update, even
+            // if the document has changed again in the meantime.
+            if doc.snapshot.as_ref().is_none_or(|(v, _)| *v < version) {
+                doc.snapshot = Some((version, snapshot));
+            }
+
+            if doc.text.version() == version {
+                doc.analysis = Some((version, analysis.clone()));
+            }
+        }
+
+        Some((version, analysis))
+    }
+
+    /// Is `version` still the latest version of the document?
+    fn is_current(&self, uri: &Url, version: i32) -> bool {
+        let documents = self.documents.lock().unwrap();
+        documents
+            .get(uri)
+            .is_some_and(|doc| doc.text.version() == version)
+    }
+
+    /// Analyse a document and publish its diagnostics.
+    ///
+    /// If the document changes while it is being analysed, the stale results
+    /// are dropped. The newer change will publish its own diagnostics.
+    async fn publish_diagnostics(&self, uri: Url) {
+        if let Some((version, analysis)) = self.analysis(&uri).await {
+            if self.is_current(&uri, version) {
+                self.client
+                    .publish_diagnostics(uri, analysis.diagnostics.clone(), Some(version))
+                    .await;
+            }
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, _params: InitializeParams) -> Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::INCREMENTAL,
+                )),
+                semantic_tokens_provider: Some(
+                    SemanticTokensOptions {
+                        legend: legend(),
+                        full: Some(SemanticTokensFullOptions::Bool(true)),
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: env!("CARGO_PKG_NAME").into(),
+                version: Some(env!("CARGO_PKG_VERSION").into()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _params: InitializedParams) {
+        self.client
+            .log_message(MessageType::INFO, "pastelito-lsp initialized")
+            .await;
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let item = params.text_document;
+        let doc = OpenDocument {
+            text: TextDocument::new(item.text, item.version),
+            format: Format::of_language_id(&item.language_id),
+            ruleset: self.ruleset(&item.uri).await,
+            snapshot: None,
+            analysis: None,
+        };
+        self.documents.lock().unwrap().insert(item.uri.clone(), doc);
+
+        self.publish_diagnostics(item.uri).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let uri = params.text_document.uri;
+        {
+            let mut documents = self.documents.lock().unwrap();
+            match documents.get_mut(&uri) {
+                Some(doc) => doc
+                    .text
+                    .apply_changes(params.content_changes, params.text_document.version),
+                None => return,
+            }
+        }
+
+        self.publish_diagnostics(uri).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.lock().unwrap().remove(&uri);
+        self.client.publish_diagnostics(uri, Vec::new(), None).await;
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let tokens = self
+            .analysis(&params.text_document.uri)
+            .await
+            .map(|(_, analysis)| {
+                SemanticTokensResult::Tokens(SemanticTokens {
+                    result_id: None,
+                    data: analysis.tokens.clone(),
+                })
+            });
+        Ok(tokens)
+    }
+}
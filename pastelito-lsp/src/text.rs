@@ -0,0 +1,134 @@
+use tower_lsp::lsp_types::{Position, TextDocumentContentChangeEvent};
+
+/// The text of an open document, kept in sync with the client.
+#[derive(Debug)]
+pub(crate) struct TextDocument {
+    text: String,
+    version: i32,
+}
+
+impl TextDocument {
+    /// Create a new document from the full text sent by the client.
+    pub(crate) fn new(text: String, version: i32) -> Self {
+        TextDocument { text, version }
+    }
+
+    /// Get the current text of the document.
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the version of the document, as reported by the client.
+    pub(crate) fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Apply a sequence of changes from a `textDocument/didChange` notification.
+    ///
+    /// Changes are applied in order. A change without a range replaces the
+    /// whole document.
+    pub(crate) fn apply_changes(
+        &mut self,
+        changes: impl IntoIterator<Item = TextDocumentContentChangeEvent>,
+        version: i32,
+    ) {
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let start = self.offset_of(range.start);
+                    let end = self.offset_of(range.end).max(start);
+                    self.text.replace_range(start..end, &change.text);
+                }
+                None => self.text = change.text,
+            }
+        }
+
+        self.version = version;
+    }
+
+    /// Convert an LSP position to a byte offset in the text.
+    ///
+    /// LSP positions count characters in UTF-16 code units. Positions past the
+    /// end of a line are clamped to the end of that line, and positions past
+    /// the end of the document are clamped to the end of the document.
+    pub(crate) fn offset_of(&self, position: Position) -> usize {
+        let mut line_start = 0;
+        for _ in 0..position.line {
+            match self.text[line_start..].find('\n') {
+                Some(i) => line_start += i + 1,
+                None => return self.text.len(),
+            }
+        }
+
+        let line = &self.text[line_start..];
+        let line = match line.find('\n') {
+            Some(i) => &line[..i],
+            None => line,
+        };
+
+        let mut utf16 = 0;
+        for (i, c) in line.char_indices() {
+            if utf16 >= position.character as usize {
+                return line_start + i;
+            }
+            utf16 += c.len_utf16();
+        }
+
+        line_start + line.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tower_lsp::lsp_types::Range;
+
+    use super::*;
+
+    fn pos(line: u32, character: u32) -> Position {
+        Position::new(line, character)
+    }
+
+    fn change(range: Option<Range>, text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range,
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_offset_of() {
+        let doc = TextDocument::new("one\ntwo\n🦕 three".into(), 0);
+        assert_eq!(doc.offset_of(pos(0, 0)), 0);
+        assert_eq!(doc.offset_of(pos(0, 2)), 2);
+        assert_eq!(doc.offset_of(pos(0, 100)), 3);
+        assert_eq!(doc.offset_of(pos(1, 0)), 4);
+        assert_eq!(doc.offset_of(pos(2, 0)), 8);
+        // The dinosaur is two UTF-16 code units and four bytes.
+        assert_eq!(doc.offset_of(pos(2, 2)), 12);
+        assert_eq!(doc.offset_of(pos(2, 3)), 13);
+        assert_eq!(doc.offset_of(pos(10, 0)), doc.text().len());
+    }
+
+    #[test]
+    fn test_apply_changes() {
+        let mut doc = TextDocument::new("The cat sat.\nOn the mat.".into(), 1);
+
+        doc.apply_changes([change(Some(Range::new(pos(0, 4), pos(0, 7))), "dog")], 2);
+        assert_eq!(doc.text(), "The dog sat.\nOn the mat.");
+        assert_eq!(doc.version(), 2);
+
+        // Insert, then delete across a line break.
+        doc.apply_changes(
+            [
+                change(Some(Range::new(pos(0, 0), pos(0, 0))), "Look! "),
+                change(Some(Range::new(pos(0, 18), pos(1, 0))), " "),
+            ],
+            3,
+        );
+        assert_eq!(doc.text(), "Look! The dog sat. On the mat.");
+
+        doc.apply_changes([change(None, "Replaced.")], 4);
+        assert_eq!(doc.text(), "Replaced.");
+    }
+}
@@ -1,6 +1,6 @@
 import * as vscode from 'vscode';
 import { Display } from './display';
-import { pastelito } from './pastelito';
+import { pastelito, Types } from './pastelito';
 import { Measurement } from './core';
 import { Memory, WasmContext } from '@vscode/wasm-component-model';
 
@@ -23,9 +23,42 @@ async function initPastelitoAPI(extensionUri: vscode.Uri): Promise<pastelito.Exp
 
 let API: pastelito.Exports | undefined = undefined;
 
-export class WASMDisplay extends Display {
+function toRange(range: Types.Range): vscode.Range {
+    return new vscode.Range(
+        range.startLine,
+        range.startCharUtf16,
+        range.endLine,
+        range.endCharUtf16
+    );
+}
+
+function toSeverity(severity: Types.Severity): vscode.DiagnosticSeverity {
+    switch (severity) {
+        case Types.Severity.error:
+            return vscode.DiagnosticSeverity.Error;
+        case Types.Severity.warning:
+            return vscode.DiagnosticSeverity.Warning;
+        case Types.Severity.info:
+            return vscode.DiagnosticSeverity.Information;
+        case Types.Severity.hint:
+            return vscode.DiagnosticSeverity.Hint;
+    }
+}
+
+// A warning from the last update of a document, with the suggested edits that
+// can be offered as quick fixes.
+type WarningWithSuggestions = {
+    diagnostic: vscode.Diagnostic,
+    suggestions: Types.Suggestion[],
+}
+
+export class WASMDisplay extends Display implements vscode.CodeActionProvider {
     diagnostics: vscode.DiagnosticCollection;
 
+    // The warnings for each document, so we can find the suggestions for the
+    // diagnostics passed to `provideCodeActions`.
+    private warningCache: Map<string, WarningWithSuggestions[]> = new Map();
+
     // Constructors can't be async, so we need to init the WASM bundle, then
     // create the display.
     public static async create(extensionUri: vscode.Uri, outputChannel: vscode.OutputChannel): Promise<WASMDisplay> {
@@ -54,11 +87,59 @@ export class WASMDisplay extends Display {
             })
         );
 
+        this.disposables.push(
+            vscode.workspace.onDidCloseTextDocument((document) => {
+                this.warningCache.delete(document.uri.toString());
+            })
+        );
+
+        this.disposables.push(
+            vscode.languages.registerCodeActionsProvider('markdown', this, {
+                providedCodeActionKinds: [vscode.CodeActionKind.QuickFix]
+            })
+        );
+
         vscode.window.visibleTextEditors.forEach((editor) => {
             this.update(editor.document);
         });
     }
 
+    provideCodeActions(
+        document: vscode.TextDocument,
+        _range: vscode.Range,
+        context: vscode.CodeActionContext
+    ): vscode.CodeAction[] {
+        const warnings = this.warningCache.get(document.uri.toString()) || [];
+        const actions: vscode.CodeAction[] = [];
+
+        for (const diagnostic of context.diagnostics) {
+            const warning = warnings.find((warning) =>
+                warning.diagnostic.range.isEqual(diagnostic.range) &&
+                warning.diagnostic.code === diagnostic.code &&
+                warning.diagnostic.message === diagnostic.message
+            );
+            if (!warning) {
+                continue;
+            }
+
+            warning.suggestions.forEach((suggestion, index) => {
+                const range = toRange(suggestion.range);
+                const title = suggestion.replacement === ''
+                    ? `Delete "${document.getText(range).trim()}"`
+                    : `Replace with "${suggestion.replacement}"`;
+
+                const action = new vscode.CodeAction(title, vscode.CodeActionKind.QuickFix);
+                action.edit = new vscode.WorkspaceEdit();
+                action.edit.replace(document.uri, range, suggestion.replacement);
+                action.diagnostics = [diagnostic];
+                action.isPreferred = index === 0;
+                actions.push(action);
+            });
+        }
+
+        return actions;
+    }
+
     private update(document: vscode.TextDocument) {
         try {
             this.update_(document);
@@ -79,25 +160,31 @@ export class WASMDisplay extends Display {
         const uri = document.uri;
         this.setMeasurements(uri, results.measurements.map(Measurement.fromWASM));
 
-        let warnings = results.warnings.map((warning) =>
-            new vscode.Diagnostic(
-                new vscode.Range(
-                    warning.range.startLine,
-                    warning.range.startCharUtf16,
-                    warning.range.endLine,
-                    warning.range.endCharUtf16
-                ),
+        const warnings = results.warnings.map((warning) => {
+            const diagnostic = new vscode.Diagnostic(
+                toRange(warning.range),
                 warning.message,
-                vscode.DiagnosticSeverity.Warning
-            )
-        );
+                toSeverity(warning.severity)
+            );
+            diagnostic.source = 'pastelito';
+            diagnostic.code = warning.ruleId;
+            return { diagnostic, suggestions: warning.suggestions };
+        });
 
-        this.diagnostics.set(uri, warnings);
+        this.warningCache.set(uri.toString(), warnings);
+        this.diagnostics.set(uri, warnings.map((warning) => warning.diagnostic));
 
         const basename = uri.fsPath.split('/').pop() || uri.fsPath;
+        const readability = results.readability;
         this.log(
             `Updated ${basename}: ${results.measurements.length} measurements, ${results.warnings.length} warnings\n` +
-            `- applyDefaultRules: ${applyElapsed}ms\n`
+            `- applyDefaultRules: ${applyElapsed}ms\n` +
+            (readability === undefined ? '' :
+                `- readability: Flesch reading ease ${readability.fleschReadingEase.toFixed(1)}, ` +
+                `Flesch-Kincaid grade ${readability.fleschKincaidGrade.toFixed(1)}, ` +
+                `Gunning fog ${readability.gunningFog.toFixed(1)}, ` +
+                `SMOG ${readability.smog.toFixed(1)}, ` +
+                `Coleman-Liau ${readability.colemanLiau.toFixed(1)}\n`)
         );
     }
 }
\ No newline at end of file
@@ -3,14 +3,22 @@
 
 mod pastelito;
 
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
 
 use crate::pastelito::Guest;
-use pastelito::vscode::pastelito::types::{Measurement, Range, Results, Warning};
-use pastelito_core::{parsers::MarkdownParser, rule::RuleSet, Document, LineCharRange};
+use pastelito::vscode::pastelito::types::{
+    BlockReadability, Measurement, Range, Readability, Results, Severity, Suggestion, Warning,
+};
+use pastelito_core::{parsers::MarkdownParser, rule::RuleSet, LineCharRange, Snapshot};
 
 static DEFAULT_RULESET: OnceLock<RuleSet> = OnceLock::new();
 
+// The snapshot from the previous call. The extension calls
+// `apply_default_rules` with the full text after every change, so we compare
+// the new text with the previous text and only re-analyse the blocks which
+// changed.
+static PREVIOUS_SNAPSHOT: Mutex<Option<Snapshot>> = Mutex::new(None);
+
 fn to_range(range: LineCharRange) -> Range {
     Range {
         start_line: range.start_line,
@@ -20,12 +28,41 @@ fn to_range(range: LineCharRange) -> Range {
     }
 }
 
-fn rule_results_to_results(results: pastelito_core::rule::Results) -> Results {
+fn to_severity(severity: pastelito_core::rule::Severity) -> Severity {
+    match severity {
+        pastelito_core::rule::Severity::Error => Severity::Error,
+        pastelito_core::rule::Severity::Warning => Severity::Warning,
+        pastelito_core::rule::Severity::Info => Severity::Info,
+        pastelito_core::rule::Severity::Hint => Severity::Hint,
+    }
+}
+
+fn to_readability(readability: pastelito_core::readability::Readability) -> Readability {
+    Readability {
+        flesch_reading_ease: readability.flesch_reading_ease,
+        flesch_kincaid_grade: readability.flesch_kincaid_grade,
+        gunning_fog: readability.gunning_fog,
+        smog: readability.smog,
+        coleman_liau: readability.coleman_liau,
+    }
+}
+
+fn rule_results_to_results(results: &pastelito_core::rule::Results) -> Results {
     let warnings = results
         .iter_warnings_with_ranges()
         .map(|(range, warning)| Warning {
             range: to_range(range),
             message: warning.message.to_owned(),
+            rule_id: warning.rule_id.to_owned(),
+            severity: to_severity(warning.severity),
+            grade: warning.grade.map(|grade| grade.value()),
+            suggestions: results
+                .iter_suggestions_with_ranges(warning)
+                .map(|(range, suggestion)| Suggestion {
+                    range: to_range(range),
+                    replacement: suggestion.replacement.to_owned(),
+                })
+                .collect(),
         })
         .collect::<Vec<_>>();
 
@@ -37,21 +74,39 @@ fn rule_results_to_results(results: pastelito_core::rule::Results) -> Results {
         })
         .collect::<Vec<_>>();
 
+    let block_readability = results
+        .iter_block_readability_with_ranges()
+        .filter_map(|(range, block)| {
+            Some(BlockReadability {
+                range: to_range(range),
+                readability: to_readability(block.counts.scores()?),
+            })
+        })
+        .collect::<Vec<_>>();
+
     Results {
         warnings,
         measurements,
+        readability: results.readability().scores().map(to_readability),
+        block_readability,
     }
 }
 
 struct Implementation;
 impl Guest for Implementation {
     fn apply_default_rules(input: String) -> Results {
-        let doc = Document::new(&MarkdownParser::default(), &input);
-
+        let parser = MarkdownParser::default();
         let ruleset = DEFAULT_RULESET.get_or_init(RuleSet::default);
 
-        let results = ruleset.apply(&doc);
-        rule_results_to_results(results)
+        let mut previous = PREVIOUS_SNAPSHOT.lock().unwrap();
+        let snapshot = match previous.take() {
+            Some(previous) => previous.update(&parser, ruleset, input),
+            None => Snapshot::new(&parser, ruleset, input),
+        };
+
+        let results = rule_results_to_results(snapshot.results());
+        *previous = Some(snapshot);
+        results
     }
 }
 
@@ -8,45 +8,115 @@ _rt::run_ctors_once();let len0 = arg1;
 let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
 let result1 = T::apply_default_rules(_rt::string_lift(bytes0));
 let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-let vscode::pastelito::types::Results{ warnings:warnings3, measurements:measurements3, } = result1;
-let vec7 = warnings3;
-let len7 = vec7.len();
-let layout7 = _rt::alloc::Layout::from_size_align_unchecked(vec7.len() * 24, 4);
-let result7 = if layout7.size() != 0 {
-  let ptr = _rt::alloc::alloc(layout7).cast::<u8>();
+let vscode::pastelito::types::Results{ warnings:warnings3, measurements:measurements3, readability:readability3, block_readability:block_readability3, } = result1;
+let vec12 = warnings3;
+let len12 = vec12.len();
+let layout12 = _rt::alloc::Layout::from_size_align_unchecked(vec12.len() * 64, 8);
+let result12 = if layout12.size() != 0 {
+  let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
   if ptr.is_null()
   {
-    _rt::alloc::handle_alloc_error(layout7);
+    _rt::alloc::handle_alloc_error(layout12);
   }
   ptr
 }else {
   ::core::ptr::null_mut()
 };
-for (i, e) in vec7.into_iter().enumerate() {
-  let base = result7.add(i * 24);
+for (i, e) in vec12.into_iter().enumerate() {
+  let base = result12.add(i * 64);
   {
-    let vscode::pastelito::types::Warning{ message:message4, range:range4, } = e;
+    let vscode::pastelito::types::Warning{ message:message4, rule_id:rule_id4, severity:severity4, grade:grade4, range:range4, suggestions:suggestions4, } = e;
     let vec5 = (message4.into_bytes()).into_boxed_slice();
     let ptr5 = vec5.as_ptr().cast::<u8>();
     let len5 = vec5.len();
     ::core::mem::forget(vec5);
     *base.add(4).cast::<usize>() = len5;
     *base.add(0).cast::<*mut u8>() = ptr5.cast_mut();
-    let vscode::pastelito::types::Range{ start_line:start_line6, start_char_utf16:start_char_utf166, end_line:end_line6, end_char_utf16:end_char_utf166, } = range4;
-    *base.add(8).cast::<i32>() = _rt::as_i32(start_line6);
-    *base.add(12).cast::<i32>() = _rt::as_i32(start_char_utf166);
-    *base.add(16).cast::<i32>() = _rt::as_i32(end_line6);
-    *base.add(20).cast::<i32>() = _rt::as_i32(end_char_utf166);
+    let vec6 = (rule_id4.into_bytes()).into_boxed_slice();
+    let ptr6 = vec6.as_ptr().cast::<u8>();
+    let len6 = vec6.len();
+    ::core::mem::forget(vec6);
+    *base.add(12).cast::<usize>() = len6;
+    *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+    *base.add(16).cast::<u8>() = (severity4.clone() as i32) as u8;
+    match grade4 {
+      Some(e) => {
+        *base.add(24).cast::<u8>() = (1i32) as u8;
+        *base.add(32).cast::<f64>() = _rt::as_f64(e);
+      },
+      None => {
+        {
+          *base.add(24).cast::<u8>() = (0i32) as u8;
+        }
+      },
+    };let vscode::pastelito::types::Range{ start_line:start_line7, start_char_utf16:start_char_utf167, end_line:end_line7, end_char_utf16:end_char_utf167, } = range4;
+    *base.add(40).cast::<i32>() = _rt::as_i32(start_line7);
+    *base.add(44).cast::<i32>() = _rt::as_i32(start_char_utf167);
+    *base.add(48).cast::<i32>() = _rt::as_i32(end_line7);
+    *base.add(52).cast::<i32>() = _rt::as_i32(end_char_utf167);
+    let vec11 = suggestions4;
+    let len11 = vec11.len();
+    let layout11 = _rt::alloc::Layout::from_size_align_unchecked(vec11.len() * 24, 4);
+    let result11 = if layout11.size() != 0 {
+      let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+      if ptr.is_null()
+      {
+        _rt::alloc::handle_alloc_error(layout11);
+      }
+      ptr
+    }else {
+      ::core::ptr::null_mut()
+    };
+    for (i, e) in vec11.into_iter().enumerate() {
+      let base = result11.add(i * 24);
+      {
+        let vscode::pastelito::types::Suggestion{ range:range8, replacement:replacement8, } = e;
+        let vscode::pastelito::types::Range{ start_line:start_line9, start_char_utf16:start_char_utf169, end_line:end_line9, end_char_utf16:end_char_utf169, } = range8;
+        *base.add(0).cast::<i32>() = _rt::as_i32(start_line9);
+        *base.add(4).cast::<i32>() = _rt::as_i32(start_char_utf169);
+        *base.add(8).cast::<i32>() = _rt::as_i32(end_line9);
+        *base.add(12).cast::<i32>() = _rt::as_i32(end_char_utf169);
+        let vec10 = (replacement8.into_bytes()).into_boxed_slice();
+        let ptr10 = vec10.as_ptr().cast::<u8>();
+        let len10 = vec10.len();
+        ::core::mem::forget(vec10);
+        *base.add(20).cast::<usize>() = len10;
+        *base.add(16).cast::<*mut u8>() = ptr10.cast_mut();
+      }
+    }
+    *base.add(60).cast::<usize>() = len11;
+    *base.add(56).cast::<*mut u8>() = result11;
   }
 }
-*ptr2.add(4).cast::<usize>() = len7;
-*ptr2.add(0).cast::<*mut u8>() = result7;
-let vec8 = (measurements3).into_boxed_slice();
-let ptr8 = vec8.as_ptr().cast::<u8>();
-let len8 = vec8.len();
-::core::mem::forget(vec8);
-*ptr2.add(12).cast::<usize>() = len8;
-*ptr2.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+*ptr2.add(4).cast::<usize>() = len12;
+*ptr2.add(0).cast::<*mut u8>() = result12;
+let vec13 = (measurements3).into_boxed_slice();
+let ptr13 = vec13.as_ptr().cast::<u8>();
+let len13 = vec13.len();
+::core::mem::forget(vec13);
+*ptr2.add(12).cast::<usize>() = len13;
+*ptr2.add(8).cast::<*mut u8>() = ptr13.cast_mut();
+match readability3 {
+  Some(e) => {
+    *ptr2.add(16).cast::<u8>() = (1i32) as u8;
+    let vscode::pastelito::types::Readability{ flesch_reading_ease:flesch_reading_ease14, flesch_kincaid_grade:flesch_kincaid_grade14, gunning_fog:gunning_fog14, smog:smog14, coleman_liau:coleman_liau14, } = e;
+    *ptr2.add(24).cast::<f64>() = _rt::as_f64(flesch_reading_ease14);
+    *ptr2.add(32).cast::<f64>() = _rt::as_f64(flesch_kincaid_grade14);
+    *ptr2.add(40).cast::<f64>() = _rt::as_f64(gunning_fog14);
+    *ptr2.add(48).cast::<f64>() = _rt::as_f64(smog14);
+    *ptr2.add(56).cast::<f64>() = _rt::as_f64(coleman_liau14);
+  },
+  None => {
+    {
+      *ptr2.add(16).cast::<u8>() = (0i32) as u8;
+    }
+  },
+};let vec15 = (block_readability3).into_boxed_slice();
+let ptr15 = vec15.as_ptr().cast::<u8>();
+let len15 = vec15.len();
+::core::mem::forget(vec15);
+*ptr2.add(68).cast::<usize>() = len15;
+*ptr2.add(64).cast::<*mut u8>() = ptr15.cast_mut();
 ptr2
 }
 #[doc(hidden)]
@@ -54,22 +124,43 @@ ptr2
 pub unsafe fn __post_return_apply_default_rules<T: Guest>(arg0: *mut u8,) {
   let l0 = *arg0.add(0).cast::<*mut u8>();
   let l1 = *arg0.add(4).cast::<usize>();
-  let base4 = l0;
-  let len4 = l1;
-  for i in 0..len4 {
-    let base = base4.add(i * 24);
+  let base11 = l0;
+  let len11 = l1;
+  for i in 0..len11 {
+    let base = base11.add(i * 64);
     {
       let l2 = *base.add(0).cast::<*mut u8>();
       let l3 = *base.add(4).cast::<usize>();
       _rt::cabi_dealloc(l2, l3, 1);
+      let l4 = *base.add(8).cast::<*mut u8>();
+      let l5 = *base.add(12).cast::<usize>();
+      _rt::cabi_dealloc(l4, l5, 1);
+      let l6 = *base.add(56).cast::<*mut u8>();
+      let l7 = *base.add(60).cast::<usize>();
+      let base10 = l6;
+      let len10 = l7;
+      for i in 0..len10 {
+        let base = base10.add(i * 24);
+        {
+          let l8 = *base.add(16).cast::<*mut u8>();
+          let l9 = *base.add(20).cast::<usize>();
+          _rt::cabi_dealloc(l8, l9, 1);
+        }
+      }
+      _rt::cabi_dealloc(base10, len10 * 24, 4);
     }
   }
-  _rt::cabi_dealloc(base4, len4 * 24, 4);
-  let l5 = *arg0.add(8).cast::<*mut u8>();
-  let l6 = *arg0.add(12).cast::<usize>();
-  let base7 = l5;
-  let len7 = l6;
-  _rt::cabi_dealloc(base7, len7 * 20, 4);
+  _rt::cabi_dealloc(base11, len11 * 64, 8);
+  let l12 = *arg0.add(8).cast::<*mut u8>();
+  let l13 = *arg0.add(12).cast::<usize>();
+  let base14 = l12;
+  let len14 = l13;
+  _rt::cabi_dealloc(base14, len14 * 20, 4);
+  let l15 = *arg0.add(64).cast::<*mut u8>();
+  let l16 = *arg0.add(68).cast::<usize>();
+  let base17 = l15;
+  let len17 = l16;
+  _rt::cabi_dealloc(base17, len17 * 56, 8);
 }
 pub trait Guest {
   fn apply_default_rules(input: _rt::String,) -> Results;
@@ -91,9 +182,9 @@ macro_rules! __export_world_pastelito_cabi{
 }
 #[doc(hidden)]
 pub(crate) use __export_world_pastelito_cabi;
-#[repr(align(4))]
-struct _RetArea([::core::mem::MaybeUninit::<u8>; 16]);
-static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+#[repr(align(8))]
+struct _RetArea([::core::mem::MaybeUninit::<u8>; 72]);
+static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 72]);
 #[allow(dead_code)]
 pub mod vscode {
   #[allow(dead_code)]
@@ -119,14 +210,73 @@ pub mod vscode {
           f.debug_struct("Range").field("start-line", &self.start_line).field("start-char-utf16", &self.start_char_utf16).field("end-line", &self.end_line).field("end-char-utf16", &self.end_char_utf16).finish()
         }
       }
+      #[derive(Clone)]
+      pub struct Suggestion {
+        pub range: Range,
+        pub replacement: _rt::String,
+      }
+      impl ::core::fmt::Debug for Suggestion {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("Suggestion").field("range", &self.range).field("replacement", &self.replacement).finish()
+        }
+      }
+      #[repr(u8)]
+      #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+      pub enum Severity {
+        Error,
+        Warning,
+        Info,
+        Hint,
+      }
+      impl ::core::fmt::Debug for Severity {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          match self {
+            Severity::Error => {
+              f.debug_tuple("Severity::Error").finish()
+            }
+            Severity::Warning => {
+              f.debug_tuple("Severity::Warning").finish()
+            }
+            Severity::Info => {
+              f.debug_tuple("Severity::Info").finish()
+            }
+            Severity::Hint => {
+              f.debug_tuple("Severity::Hint").finish()
+            }
+          }
+        }
+      }
+
+      impl Severity{
+        #[doc(hidden)]
+        pub unsafe fn _lift(val: u8) -> Severity{
+          if !cfg!(debug_assertions) {
+            return ::core::mem::transmute(val);
+          }
+
+          match val {
+            0 => Severity::Error,
+            1 => Severity::Warning,
+            2 => Severity::Info,
+            3 => Severity::Hint,
+
+            _ => panic!("invalid enum discriminant"),
+          }
+        }
+      }
+
       #[derive(Clone)]
       pub struct Warning {
         pub message: _rt::String,
+        pub rule_id: _rt::String,
+        pub severity: Severity,
+        pub grade: Option<f64>,
         pub range: Range,
+        pub suggestions: _rt::Vec::<Suggestion>,
       }
       impl ::core::fmt::Debug for Warning {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-          f.debug_struct("Warning").field("message", &self.message).field("range", &self.range).finish()
+          f.debug_struct("Warning").field("message", &self.message).field("rule-id", &self.rule_id).field("severity", &self.severity).field("grade", &self.grade).field("range", &self.range).field("suggestions", &self.suggestions).finish()
         }
       }
       #[repr(C)]
@@ -140,14 +290,41 @@ pub mod vscode {
           f.debug_struct("Measurement").field("key", &self.key).field("range", &self.range).finish()
         }
       }
+      #[repr(C)]
+      #[derive(Clone, Copy)]
+      pub struct Readability {
+        pub flesch_reading_ease: f64,
+        pub flesch_kincaid_grade: f64,
+        pub gunning_fog: f64,
+        pub smog: f64,
+        pub coleman_liau: f64,
+      }
+      impl ::core::fmt::Debug for Readability {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("Readability").field("flesch-reading-ease", &self.flesch_reading_ease).field("flesch-kincaid-grade", &self.flesch_kincaid_grade).field("gunning-fog", &self.gunning_fog).field("smog", &self.smog).field("coleman-liau", &self.coleman_liau).finish()
+        }
+      }
+      #[repr(C)]
+      #[derive(Clone, Copy)]
+      pub struct BlockReadability {
+        pub range: Range,
+        pub readability: Readability,
+      }
+      impl ::core::fmt::Debug for BlockReadability {
+        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+          f.debug_struct("BlockReadability").field("range", &self.range).field("readability", &self.readability).finish()
+        }
+      }
       #[derive(Clone)]
       pub struct Results {
         pub warnings: _rt::Vec::<Warning>,
         pub measurements: _rt::Vec::<Measurement>,
+        pub readability: Option<Readability>,
+        pub block_readability: _rt::Vec::<BlockReadability>,
       }
       impl ::core::fmt::Debug for Results {
         fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-          f.debug_struct("Results").field("warnings", &self.warnings).field("measurements", &self.measurements).finish()
+          f.debug_struct("Results").field("warnings", &self.warnings).field("measurements", &self.measurements).field("readability", &self.readability).field("block-readability", &self.block_readability).finish()
         }
       }
 
@@ -171,6 +348,27 @@ mod _rt {
     }
   }
   
+  pub fn as_f64<T: AsF64>(t: T) -> f64 {
+    t.as_f64()
+  }
+
+  pub trait AsF64 {
+    fn as_f64(self) -> f64;
+  }
+
+  impl<'a, T: Copy + AsF64> AsF64 for &'a T {
+    fn as_f64(self) -> f64 {
+      (*self).as_f64()
+    }
+  }
+  
+  impl AsF64 for f64 {
+    #[inline]
+    fn as_f64(self) -> f64 {
+      self as f64
+    }
+  }
+  
   pub fn as_i32<T: AsI32>(t: T) -> i32 {
     t.as_i32()
   }
@@ -282,16 +480,22 @@ pub(crate) use __export_pastelito_impl as export;
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.34.0:vscode:pastelito:pastelito:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 436] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb4\x02\x01A\x02\x01\
-A\x06\x01B\x0a\x01r\x04\x0astart-liney\x10start-char-utf16y\x08end-liney\x0eend-\
-char-utf16y\x04\0\x05range\x03\0\0\x01r\x02\x07messages\x05range\x01\x04\0\x07wa\
-rning\x03\0\x02\x01r\x02\x03keyy\x05range\x01\x04\0\x0bmeasurement\x03\0\x04\x01\
-p\x03\x01p\x05\x01r\x02\x08warnings\x06\x0cmeasurements\x07\x04\0\x07results\x03\
-\0\x08\x03\0\x16vscode:pastelito/types\x05\0\x02\x03\0\0\x07results\x03\0\x07res\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 741] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xe5\x04\x01A\x02\x01\
+A\x06\x01B\x16\x01r\x04\x0astart-liney\x10start-char-utf16y\x08end-liney\x0eend-\
+char-utf16y\x04\0\x05range\x03\0\0\x01r\x02\x05range\x01\x0breplacements\x04\0\x0a\
+suggestion\x03\0\x02\x01m\x04\x05error\x07warning\x04info\x04hint\x04\0\x08sever\
+ity\x03\0\x04\x01ku\x01p\x03\x01r\x06\x07messages\x07rule-ids\x08severity\x05\x05\
+grade\x06\x05range\x01\x0bsuggestions\x07\x04\0\x07warning\x03\0\x08\x01r\x02\x03\
+keyy\x05range\x01\x04\0\x0bmeasurement\x03\0\x0a\x01r\x05\x13flesch-reading-ease\
+u\x14flesch-kincaid-gradeu\x0bgunning-fogu\x04smogu\x0ccoleman-liauu\x04\0\x0bre\
+adability\x03\0\x0c\x01r\x02\x05range\x01\x0breadability\x0d\x04\0\x11block-read\
+ability\x03\0\x0e\x01p\x09\x01p\x0b\x01k\x0d\x01p\x0f\x01r\x04\x08warnings\x10\x0c\
+measurements\x11\x0breadability\x12\x11block-readability\x13\x04\0\x07results\x03\
+\0\x14\x03\0\x16vscode:pastelito/types\x05\0\x02\x03\0\0\x07results\x03\0\x07res\
 ults\x03\0\x01\x01@\x01\x05inputs\0\x02\x04\0\x13apply-default-rules\x01\x03\x04\
 \0\x1avscode:pastelito/pastelito\x04\0\x0b\x0f\x01\0\x09pastelito\x03\0\0\0G\x09\
-producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.219.1\x10wit-bindgen-rus\
+producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.219.2\x10wit-bindgen-rus\
 t\x060.34.0";
 
 #[inline(never)]
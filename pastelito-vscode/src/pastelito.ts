@@ -4,7 +4,7 @@
  *--------------------------------------------------------------------------------------------*/
 /* eslint-disable @typescript-eslint/ban-types */
 import * as $wcm from '@vscode/wasm-component-model';
-import type { u32, i32, ptr } from '@vscode/wasm-component-model';
+import type { u32, float64, i32, ptr } from '@vscode/wasm-component-model';
 
 export namespace Types {
 	export type Range = {
@@ -14,9 +14,25 @@ export namespace Types {
 		endCharUtf16: u32;
 	};
 
+	export type Suggestion = {
+		range: Range;
+		replacement: string;
+	};
+
+	export enum Severity {
+		error = 'error',
+		warning = 'warning',
+		info = 'info',
+		hint = 'hint'
+	}
+
 	export type Warning = {
 		message: string;
+		ruleId: string;
+		severity: Severity;
+		grade?: float64 | undefined;
 		range: Range;
+		suggestions: Suggestion[];
 	};
 
 	export type Measurement = {
@@ -24,9 +40,24 @@ export namespace Types {
 		range: Range;
 	};
 
+	export type Readability = {
+		fleschReadingEase: float64;
+		fleschKincaidGrade: float64;
+		gunningFog: float64;
+		smog: float64;
+		colemanLiau: float64;
+	};
+
+	export type BlockReadability = {
+		range: Range;
+		readability: Readability;
+	};
+
 	export type Results = {
 		warnings: Warning[];
 		measurements: Measurement[];
+		readability?: Readability | undefined;
+		blockReadability: BlockReadability[];
 	};
 }
 export type Types = {
@@ -59,17 +90,39 @@ export namespace Types.$ {
 		['endLine', $wcm.u32],
 		['endCharUtf16', $wcm.u32],
 	]);
+	export const Suggestion = new $wcm.RecordType<Types.Suggestion>([
+		['range', Range],
+		['replacement', $wcm.wstring],
+	]);
+	export const Severity = new $wcm.EnumType<Types.Severity>(['error', 'warning', 'info', 'hint']);
 	export const Warning = new $wcm.RecordType<Types.Warning>([
 		['message', $wcm.wstring],
+		['ruleId', $wcm.wstring],
+		['severity', Severity],
+		['grade', new $wcm.OptionType<float64>($wcm.float64)],
 		['range', Range],
+		['suggestions', new $wcm.ListType<Types.Suggestion>(Suggestion)],
 	]);
 	export const Measurement = new $wcm.RecordType<Types.Measurement>([
 		['key', $wcm.u32],
 		['range', Range],
 	]);
+	export const Readability = new $wcm.RecordType<Types.Readability>([
+		['fleschReadingEase', $wcm.float64],
+		['fleschKincaidGrade', $wcm.float64],
+		['gunningFog', $wcm.float64],
+		['smog', $wcm.float64],
+		['colemanLiau', $wcm.float64],
+	]);
+	export const BlockReadability = new $wcm.RecordType<Types.BlockReadability>([
+		['range', Range],
+		['readability', Readability],
+	]);
 	export const Results = new $wcm.RecordType<Types.Results>([
 		['warnings', new $wcm.ListType<Types.Warning>(Warning)],
 		['measurements', new $wcm.ListType<Types.Measurement>(Measurement)],
+		['readability', new $wcm.OptionType<Types.Readability>(Readability)],
+		['blockReadability', new $wcm.ListType<Types.BlockReadability>(BlockReadability)],
 	]);
 }
 export namespace Types._ {
@@ -77,8 +130,12 @@ export namespace Types._ {
 	export const witName = 'types' as const;
 	export const types: Map<string, $wcm.AnyComponentModelType> = new Map<string, $wcm.AnyComponentModelType>([
 		['Range', $.Range],
+		['Suggestion', $.Suggestion],
+		['Severity', $.Severity],
 		['Warning', $.Warning],
 		['Measurement', $.Measurement],
+		['Readability', $.Readability],
+		['BlockReadability', $.BlockReadability],
 		['Results', $.Results]
 	]);
 	export type WasmInterface = {